@@ -98,27 +98,27 @@ fn red_animate(animator: &mut Animator) {
         .set_exec(true)
         .add_change()
         //.set_repeat(Repeat::Finite(5))
-        .set_delay(Duration::from_secs(1))
+        .set_duration(Duration::from_secs(1))
         .set_ease(EaseFunction::QuadraticInOut.into())
-        .set_rotation(Quat::from_array([0., 0., 1., 0.]))
+        .set_rotation_axis(Vec3::Z, 90.)
         .set_translation(Vec3::new(520., 0., 0.));
     animator
         .add_change()
-        .set_delay(Duration::from_secs(1))
+        .set_duration(Duration::from_secs(1))
         .set_ease(EaseFunction::QuadraticInOut.into())
-        .set_rotation(Quat::from_array([0., 0., -1., 0.]))
+        .set_rotation_axis(Vec3::Z, -90.)
         .set_translation(Vec3::new(0., 520., 0.));
     animator
         .add_change()
-        .set_delay(Duration::from_secs(1))
+        .set_duration(Duration::from_secs(1))
         .set_ease(EaseFunction::QuadraticInOut.into())
-        .set_rotation(Quat::from_array([0., 0., 1., 0.]))
+        .set_rotation_axis(Vec3::Z, 90.)
         .set_translation(Vec3::new(-520., 0., 0.));
     animator
         .add_change()
         .set_ease(EaseFunction::QuadraticInOut.into())
-        .set_delay(Duration::from_secs(1))
-        .set_rotation(Quat::from_array([0., 0., -1., 0.]))
+        .set_duration(Duration::from_secs(1))
+        .set_rotation_axis(Vec3::Z, -90.)
         .set_translation(Vec3::new(0., -520., 0.));
 }
 
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+/// Mismatched units keep `a`, animators convert a step's start into the unit of its
+/// target first, see [`Animatable::match_unit`](crate::lens::Animatable::match_unit).
 pub fn add_val(a: Val, b: Val) -> Val {
     match (a, b) {
         (Val::Px(a), Val::Px(b)) => Val::Px(a + b),
@@ -24,9 +26,209 @@ pub fn sub_val(a: Val, b: Val) -> Val {
     }
 }
 
+pub fn mul_val(a: Val, b: f32) -> Val {
+    match a {
+        Val::Px(a) => Val::Px(a * b),
+        Val::Percent(a) => Val::Percent(a * b),
+        Val::Vw(a) => Val::Vw(a * b),
+        Val::Vh(a) => Val::Vh(a * b),
+        Val::VMin(a) => Val::VMin(a * b),
+        Val::VMax(a) => Val::VMax(a * b),
+        _ => a,
+    }
+}
+
+pub fn add_color(a: Color, b: Color) -> Color {
+    let a_: Vec4 = a.into();
+    let b_: Vec4 = b.into();
+    (a_ + b_).into()
+}
+
 pub fn sub_color(a: Color, b: Color) -> Color {
     let a_:Vec4 = a.into();
     let b_:Vec4 = b.into();
     (a_ - b_).into()
 }
 
+pub fn mul_color(a: Color, b: f32) -> Color {
+    let a_: Vec4 = a.into();
+    (a_ * b).into()
+}
+
+/// Space an [`Animator`](crate::animator::Animator) interpolates a color track in, see
+/// [`Animation::with_color_space`](crate::animator::Animation::with_color_space).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Raw sRGB components.
+    #[default]
+    Srgb,
+    /// Linear RGB with premultiplied alpha.
+    LinearRgb,
+    /// HSL along the shortest way around the hue circle.
+    Hsl,
+    /// HSV along the shortest way around the hue circle.
+    Hsv,
+    /// Oklab with premultiplied alpha, perceptually even.
+    Oklab,
+}
+
+pub fn lerp_color(a: Color, b: Color, ratio: f32, space: ColorSpace) -> Color {
+    match space {
+        ColorSpace::Srgb => add_color(a, mul_color(sub_color(b, a), ratio)),
+        ColorSpace::LinearRgb => {
+            let [r, g, b, a] = lerp_premultiplied(
+                a.as_linear_rgba_f32().into(),
+                b.as_linear_rgba_f32().into(),
+                ratio,
+            )
+            .to_array();
+            Color::rgba_linear(r, g, b, a)
+        }
+        ColorSpace::Oklab => {
+            let lab = lerp_premultiplied(to_oklab(a), to_oklab(b), ratio);
+            from_oklab(lab)
+        }
+        ColorSpace::Hsl => {
+            let [h, s, l, a] = lerp_hue_space(a.as_hsla_f32(), b.as_hsla_f32(), ratio);
+            Color::hsla(h, s, l, a)
+        }
+        ColorSpace::Hsv => {
+            let [h, s, v, a] = lerp_hue_space(to_hsva(a), to_hsva(b), ratio);
+            from_hsva(h, s, v, a)
+        }
+    }
+}
+
+/// Interpolates color components weighted by alpha so transparent ends don't bleed
+/// their color into the other.
+fn lerp_premultiplied(a: Vec4, b: Vec4, ratio: f32) -> Vec4 {
+    let premultiplied = |c: Vec4| (c.truncate() * c.w).extend(c.w);
+    let mixed = premultiplied(a).lerp(premultiplied(b), ratio);
+    if mixed.w > 0. {
+        (mixed.truncate() / mixed.w).extend(mixed.w)
+    } else {
+        mixed
+    }
+}
+
+/// Interpolates `[hue, saturation, value or lightness, alpha]` with saturation and
+/// lightness premultiplied by alpha, a gray or transparent end takes the hue of the other.
+fn lerp_hue_space(a: [f32; 4], b: [f32; 4], ratio: f32) -> [f32; 4] {
+    let missing = |c: [f32; 4]| c[1] == 0. || c[3] == 0.;
+    let (mut from, mut to) = (a[0], b[0]);
+    if missing(a) {
+        from = to;
+    } else if missing(b) {
+        to = from;
+    }
+    let delta = (to - from + 540.).rem_euclid(360.) - 180.;
+    let [_, s, l, alpha] = lerp_premultiplied(
+        Vec4::new(0., a[1], a[2], a[3]),
+        Vec4::new(0., b[1], b[2], b[3]),
+        ratio,
+    )
+    .to_array();
+    [(from + delta * ratio).rem_euclid(360.), s, l, alpha]
+}
+
+fn to_oklab(color: Color) -> Vec4 {
+    let [r, g, b, a] = color.as_linear_rgba_f32();
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+    Vec4::new(
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        a,
+    )
+}
+
+fn from_oklab(lab: Vec4) -> Color {
+    let l = (lab.x + 0.396_337_78 * lab.y + 0.215_803_76 * lab.z).powi(3);
+    let m = (lab.x - 0.105_561_346 * lab.y - 0.063_854_17 * lab.z).powi(3);
+    let s = (lab.x - 0.089_484_18 * lab.y - 1.291_485_5 * lab.z).powi(3);
+    Color::rgba_linear(
+        4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+        -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+        -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+        lab.w,
+    )
+}
+
+fn to_hsva(color: Color) -> [f32; 4] {
+    let [r, g, b, a] = color.as_rgba_f32();
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+    let hue = if delta == 0. {
+        0.
+    } else if max == r {
+        60. * ((g - b) / delta).rem_euclid(6.)
+    } else if max == g {
+        60. * ((b - r) / delta + 2.)
+    } else {
+        60. * ((r - g) / delta + 4.)
+    };
+    let saturation = if max == 0. { 0. } else { delta / max };
+    [hue, saturation, max, a]
+}
+
+fn from_hsva(hue: f32, saturation: f32, value: f32, alpha: f32) -> Color {
+    let channel = |n: f32| {
+        let k = (n + hue / 60.).rem_euclid(6.);
+        value - value * saturation * k.min(4. - k).clamp(0., 1.)
+    };
+    Color::rgba(channel(5.), channel(3.), channel(1.), alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: Color, expected: Color) {
+        let (actual, expected) = (Vec4::from(actual), Vec4::from(expected));
+        assert!(actual.abs_diff_eq(expected, 1e-3), "{actual} != {expected}");
+    }
+
+    const SPACES: [ColorSpace; 5] = [
+        ColorSpace::Srgb,
+        ColorSpace::LinearRgb,
+        ColorSpace::Oklab,
+        ColorSpace::Hsl,
+        ColorSpace::Hsv,
+    ];
+
+    #[test]
+    fn every_space_keeps_the_endpoints() {
+        let (from, to) = (
+            Color::rgba(0.9, 0.2, 0.1, 1.),
+            Color::rgba(0.1, 0.4, 0.8, 0.5),
+        );
+        for space in SPACES {
+            assert_near(lerp_color(from, to, 0., space), from);
+            assert_near(lerp_color(from, to, 1., space), to);
+        }
+    }
+
+    #[test]
+    fn transparent_end_does_not_bleed() {
+        let clear_blue = Color::rgba(0., 0., 1., 0.);
+        for space in SPACES.into_iter().skip(1) {
+            let mixed = lerp_color(Color::RED, clear_blue, 0.5, space);
+            assert_near(mixed, Color::rgba(1., 0., 0., 0.5));
+        }
+    }
+
+    #[test]
+    fn hue_takes_the_shorter_way_around() {
+        let mixed = lerp_color(
+            Color::hsl(350., 1., 0.5),
+            Color::hsl(10., 1., 0.5),
+            0.5,
+            ColorSpace::Hsl,
+        );
+        assert_near(mixed, Color::hsl(0., 1., 0.5));
+        let mixed = lerp_color(Color::RED, Color::GRAY, 0.5, ColorSpace::Hsv);
+        assert!(mixed.r() > mixed.g() && (mixed.g() - mixed.b()).abs() < 1e-3);
+    }
+}
@@ -1,28 +1,100 @@
-use crate::ani_utils::*;
-use bevy::prelude::*;
+use crate::{ani_utils::ColorSpace, lens::*, reflect::*};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 use interpolation::*;
 use std::{
-    ops::{Add, Sub},
+    any::{Any, TypeId},
+    f32::consts::TAU,
+    fmt,
+    sync::Arc,
     time::Duration,
 };
 
-#[derive(Component, Default, Debug)]
+#[derive(Component, Debug)]
 pub struct Animator {
-    total_times: f32,
     exectable: bool,
     exec_index: usize,
     exec_loop: bool,
     exec_step: bool,
-    forward: bool,
+    speed: f32,
+    elapsed: f32,
     progress: f32,
-    fract: f32,
-    init_style: bool,
-    init_text: bool,
-    init_background: bool,
-    init_transform: bool,
-    init_sprite: bool,
-    start: Anim,
+    started: bool,
+    finished: bool,
+    start: StartMap,
+    /// Starts of each step as it was last entered going forwards, restored when going back.
+    saved: HashMap<usize, StartMap>,
+    begun: HashSet<TrackKey>,
     animations: Vec<Animation>,
+    events: Vec<AnimatorEventKind>,
+    parallel: Vec<Parallel>,
+    velocity: StartMap,
+    settling: HashSet<TrackKey>,
+    /// Springs written since the last tick, the others can't come to rest.
+    reached: HashSet<TrackKey>,
+    /// Whether the properties were written since `settling` was reset, so `reached` is
+    /// complete.
+    reach_checked: bool,
+    dt: f32,
+    blend: f32,
+    blend_from: StartMap,
+    wait: f32,
+    layout: Option<ValContext>,
+    awaiting_layout: bool,
+}
+
+/// Sent by [`AnimationPlugin`](crate::plugin::AnimationPlugin) whenever an [`Animator`]
+/// changes playback state.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct AnimatorEvent {
+    pub entity: Entity,
+    pub kind: AnimatorEventKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnimatorEventKind {
+    /// The animator ticked for the first time since it was (re)started.
+    Started,
+    /// Playback moved from one [`Animation`] to another.
+    StepAdvanced { from: usize, to: usize },
+    /// A looping animator wrapped around to its first [`Animation`].
+    LoopWrapped,
+    /// The last [`Animation`] completed and playback stopped.
+    Finished,
+}
+
+impl Default for Animator {
+    fn default() -> Self {
+        Self {
+            exectable: false,
+            exec_index: 0,
+            exec_loop: false,
+            exec_step: false,
+            speed: 1.,
+            elapsed: 0.,
+            progress: 0.,
+            started: false,
+            finished: false,
+            start: StartMap::default(),
+            saved: HashMap::default(),
+            begun: HashSet::default(),
+            animations: Vec::new(),
+            events: Vec::new(),
+            parallel: Vec::new(),
+            velocity: StartMap::default(),
+            settling: HashSet::default(),
+            reached: HashSet::default(),
+            reach_checked: false,
+            dt: 0.,
+            blend: 0.,
+            blend_from: StartMap::default(),
+            wait: 0.,
+            layout: None,
+            awaiting_layout: false,
+        }
+    }
 }
 
 impl Animator {
@@ -41,381 +113,655 @@ impl Animator {
         self
     }
 
+    /// Time to hold still before the animator starts playing, e.g. to stagger siblings.
+    pub fn set_start_delay(&mut self, delay: Duration) -> &mut Self {
+        self.wait = delay.as_secs_f32();
+        self
+    }
+
     pub fn add_change(&mut self) -> &mut Animation {
         self.animations.push(Animation::default());
         self.animations.last_mut().unwrap()
     }
 
+    /// Adds an [`Animation`] that plays alongside the sequence with its own timing, its
+    /// offsets are added on top of whatever else animates the same property.
+    ///
+    /// It starts with the sequence, keeps its end values once done and plays again when
+    /// the sequence loops or restarts.
+    pub fn add_parallel(&mut self) -> &mut Animation {
+        self.parallel.push(Parallel::default());
+        &mut self.parallel.last_mut().unwrap().animation
+    }
+
     pub fn add_change_start(&mut self, index: usize) {
-        self.start = self.start + self.animations[self.exec_index].change;
+        if !self.has_step(index) {
+            return;
+        }
+        let animation = &self.animations[self.exec_index];
+        animation.shift_to_end(&mut self.start);
         self.start(index);
     }
 
+    /// Number of steps added with [`Animator::add_change`].
+    pub fn step_count(&self) -> usize {
+        self.animations.len()
+    }
+
+    /// Index of the step playing right now.
+    pub fn current_step(&self) -> usize {
+        self.exec_index
+    }
+
     pub fn progress(&self) -> f32 {
         self.progress
     }
 
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Drains the events recorded since the last call.
+    pub fn drain_events(&mut self) -> impl Iterator<Item = AnimatorEventKind> + '_ {
+        self.events.drain(..)
+    }
+
+    /// Plays step `index` from its beginning, out of range steps are ignored with a warning.
     pub fn start(&mut self, index: usize) {
-        self.reset_progress();
-        if self.exec_index == index {
+        if !self.has_step(index) {
             return;
         }
+        self.restart(index);
+        self.seek_parallel(self.step_offset(index));
+    }
+
+    /// Moves playback to step `index` in whichever direction it lies, the animator
+    /// starts over if it had finished.
+    fn restart(&mut self, index: usize) {
+        if self.finished {
+            self.started = false;
+        }
+        self.enter(index, index >= self.exec_index);
+    }
+
+    /// Moves playback to step `index`, keeping the playback state of the sequence.
+    ///
+    /// Steps entered going `forward` capture their absolute starts again, otherwise
+    /// they keep the starts saved when they were last entered going forwards.
+    fn enter(&mut self, index: usize, forward: bool) {
+        self.reset_progress();
+        self.finished = false;
         if self.exec_index > index {
-            for i in index..self.exec_index {
-                self.start = self.start - (&self.animations[i]).change;
+            for step in (index..self.exec_index).rev() {
+                let saved = self.saved.get(&step);
+                self.animations[step].restore(&mut self.start, saved);
             }
         } else if self.exec_index < index {
-            for i in self.exec_index..index {
-                self.start = self.start + (&self.animations[i]).change;
+            for step in self.exec_index..index {
+                let animation = &self.animations[step];
+                if forward {
+                    animation.save(&self.start, self.saved.entry(step).or_default());
+                }
+                animation.shift_to_end(&mut self.start);
             }
         }
+        if let Some(saved) = self.saved.get(&index).filter(|_| !forward) {
+            let animation = &self.animations[index];
+            animation.restore(&mut self.start, Some(saved));
+            self.begun
+                .extend(animation.tracks.iter().map(|track| track.key()));
+        }
         self.exec_index = index;
+        self.reset_settling();
+        self.update_progress();
+    }
+
+    /// Interrupts the current step and plays `index` from the properties' current values
+    /// instead of jumping to where the step nominally starts.
+    ///
+    /// With a zero `blend` the new step's deltas and targets apply from the current
+    /// values. Otherwise the step plays from its nominal start and is cross-faded in from
+    /// the interrupted values over `blend`, which suits hover in / hover out pairs.
+    pub fn transition(&mut self, index: usize, blend: Duration) {
+        if !self.has_step(index) {
+            return;
+        }
+        self.restart(index);
+        if blend.is_zero() {
+            if let Some(animation) = self.animations.get(index) {
+                for track in &animation.tracks {
+                    self.start.remove(&track.key());
+                }
+            }
+        } else {
+            self.blend = blend.as_secs_f32();
+        }
+    }
+
+    fn has_step(&self, index: usize) -> bool {
+        let len = self.animations.len();
+        if index >= len {
+            warn!("ignored step {index} of an Animator with {len} steps");
+        }
+        index < len
+    }
+
+    /// Marks the springs of the current step as moving, dropping the velocity of
+    /// properties that stopped springing.
+    fn reset_settling(&mut self) {
+        self.settling = self
+            .animations
+            .get(self.exec_index)
+            .into_iter()
+            .flat_map(|animation| &animation.tracks)
+            .filter(|track| track.spring().is_some())
+            .map(|track| track.key())
+            .collect();
+        let settling = &self.settling;
+        self.velocity.retain(|key| settling.contains(key));
+        self.reached.clear();
+        self.reach_checked = false;
     }
 
-    fn add_delta(&mut self, delta: Duration) {
+    /// Stops waiting for springs no component was written for, e.g. because the entity
+    /// lacks the component or its lens was never registered.
+    fn drop_unreached(&mut self) {
+        if self.reach_checked {
+            let reached = &self.reached;
+            self.settling.retain(|key| reached.contains(key));
+        }
+        self.reached.clear();
+        self.reach_checked = true;
+    }
+
+    /// Sizes [`Val`] starts are converted against, kept up to date for UI nodes by
+    /// [`AnimationPlugin`](crate::plugin::AnimationPlugin).
+    pub fn set_layout(&mut self, layout: ValContext) -> &mut Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Holds the animator until its node got its first layout, so starts aren't read
+    /// from or converted against a node that has no size yet.
+    pub(crate) fn await_layout(&mut self, waiting: bool) {
+        self.awaiting_layout = waiting;
+    }
+
+    pub(crate) fn is_awaiting_layout(&self) -> bool {
+        self.awaiting_layout
+    }
+
+    /// Whether the current step played to its end and its springs came to rest.
+    pub fn is_step_complete(&self) -> bool {
+        self.animations
+            .get(self.exec_index)
+            .map_or(true, |animation| self.elapsed >= animation.duration_secs())
+            && self.is_at_rest()
+    }
+
+    /// Whether every [`Spring`] of the current step has come to rest.
+    pub fn is_at_rest(&self) -> bool {
+        self.settling.is_empty()
+    }
+
+    /// Stops advancing without touching the current position.
+    pub fn pause(&mut self) -> &mut Self {
+        self.exectable = false;
+        self
+    }
+
+    /// Continues from the position [`Animator::pause`] stopped at.
+    pub fn resume(&mut self) -> &mut Self {
+        self.exectable = true;
+        self
+    }
+
+    pub fn is_paused(&self) -> bool {
+        !self.exectable
+    }
+
+    /// Playback rate multiplier, negative values play the sequence backwards.
+    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed;
+        self
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Total length of the sequence and its parallel animations, `None` if any
+    /// [`Animation`] repeats forever. Steps with a [`Spring`] can run past it while they
+    /// settle.
+    pub fn duration(&self) -> Option<Duration> {
+        let secs = self
+            .parallel
+            .iter()
+            .map(|parallel| parallel.animation.duration_secs())
+            .fold(self.step_offset(self.animations.len()), f32::max);
+        secs.is_finite().then(|| to_duration(secs))
+    }
+
+    /// Time played since the beginning of the sequence.
+    pub fn elapsed(&self) -> Duration {
+        let secs = self
+            .parallel
+            .iter()
+            .map(|parallel| parallel.elapsed)
+            .fold(self.step_offset(self.exec_index) + self.elapsed, f32::max);
+        to_duration(secs)
+    }
+
+    /// Length of the steps before `index`.
+    fn step_offset(&self, index: usize) -> f32 {
+        self.animations[..index.min(self.animations.len())]
+            .iter()
+            .map(Animation::duration_secs)
+            .sum()
+    }
+
+    pub fn remaining(&self) -> Option<Duration> {
+        self.duration()
+            .map(|duration| duration.saturating_sub(self.elapsed()))
+    }
+
+    /// Jumps to an absolute time measured from the beginning of the sequence, seeking
+    /// within the current step keeps the values it started from.
+    pub fn seek(&mut self, time: Duration) {
+        let total = time.as_secs_f32();
+        if !self.animations.is_empty() {
+            let mut time = total;
+            let mut index = 0;
+            while index + 1 < self.animations.len() {
+                let length = self.animations[index].duration_secs();
+                if time < length {
+                    break;
+                }
+                time -= length;
+                index += 1;
+            }
+            if index != self.exec_index || self.finished {
+                self.restart(index);
+            }
+            self.elapsed = time.min(self.animations[index].duration_secs());
+            self.update_progress();
+        }
+        self.seek_parallel(total);
+    }
+
+    /// Lets the springs of a paused animator settle by `delta`, for animators positioned
+    /// with [`Animator::seek`] by a [`Timeline`](crate::timeline::Timeline).
+    pub(crate) fn settle_paused(&mut self, delta: Duration) {
+        self.dt = delta.as_secs_f32();
+    }
+
+    /// Jumps to a position in `0..=1` of [`Animator::duration`], ignored for infinite sequences.
+    pub fn seek_normalized(&mut self, position: f32) {
+        if let Some(duration) = self.duration() {
+            self.seek(duration.mul_f32(position.clamp(0., 1.)));
+        }
+    }
+
+    fn update_progress(&mut self) {
         if let Some(animation) = self.animations.get(self.exec_index) {
-            self.progress =
-                self.progress + (delta.as_secs_f64() / animation.per.as_secs_f64()) as f32;
-            self.fract = self.progress.fract();
-            self.forward = self.progress.trunc() as u8 % 2 == 0;
+            self.progress = animation.progress_at(self.elapsed);
         }
     }
 
     fn reset_progress(&mut self) {
+        self.begun.clear();
+        self.blend = 0.;
+        self.blend_from = StartMap::default();
+        self.elapsed = 0.;
         self.progress = 0.;
-        self.fract = 0.;
-        self.forward = true;
     }
 
+    /// Repetitions of the current step.
     pub fn total_times(&self) -> f32 {
-        self.total_times
+        self.animations
+            .get(self.exec_index)
+            .map_or(0., |animation| animation.total_times)
     }
 
     pub fn tick_progress(&mut self, delta: Duration) {
+        self.dt = 0.;
+        if self.awaiting_layout {
+            return;
+        }
+        self.drop_unreached();
         if !self.exectable {
             return;
         }
-        if self.progress == 0. {
-            self.total_times = self.animations[self.exec_index].total_times;
-            self.add_delta(delta);
-        } else if self.progress < self.total_times {
-            self.add_delta(delta);
-        } else if self.exec_step {
-            self.progress = self.total_times;
-        } else if self.exec_loop {
-            self.start((self.exec_index + 1) % self.animations.len());
-        } else if self.exec_index + 1 == self.animations.len() {
-            self.progress = self.total_times;
+        let delta = delta.as_secs_f32() * self.speed;
+        if self.wait > 0. {
+            self.wait -= delta.abs();
+            return;
+        }
+        self.dt = delta.abs();
+        self.tick_parallel(delta);
+        if self.animations.is_empty() {
+            return;
+        }
+        // The first step gets applied once even if it has no length to play.
+        let starting = !self.started;
+        if starting {
+            self.started = true;
+            self.reset_settling();
+            self.events.push(AnimatorEventKind::Started);
+        }
+        let length = self.animations[self.exec_index].duration_secs();
+        if starting {
+            self.elapsed = (self.elapsed + delta).clamp(0., length);
+            self.update_progress();
+        } else if delta >= 0. && self.elapsed >= length && self.is_at_rest() {
+            self.step_end();
+        } else if delta < 0. && self.elapsed <= 0. {
+            self.step_begin();
         } else {
-            self.start(self.exec_index + 1);
+            self.elapsed = (self.elapsed + delta).clamp(0., length);
+            self.update_progress();
         }
     }
 
-    pub fn init_style(&mut self, style: &mut Style) {
-        if !self.init_style {
-            self.start.style_width = style.width;
-            self.start.style_height = style.height;
-            self.start.style_left = style.left;
-            self.start.style_right = style.right;
-            self.start.style_top = style.top;
-            self.start.style_bottom = style.bottom;
-            self.init_style = true;
+    /// Advances the parallel animations, they keep their end values once done.
+    fn tick_parallel(&mut self, delta: f32) {
+        for parallel in &mut self.parallel {
+            let length = parallel.animation.duration_secs();
+            parallel.elapsed = (parallel.elapsed + delta).clamp(0., length);
         }
     }
 
-    pub fn init_text(&mut self, text: &mut Text) {
-        if !self.init_text {
-            if let Some(section) = text.sections.get(0) {
-                self.start.text_font_size = section.style.font_size;
-                self.start.text_font_color = section.style.color;
-            }
-            self.init_text = true;
+    /// Moves the parallel animations to `time` from the beginning of the sequence.
+    fn seek_parallel(&mut self, time: f32) {
+        for parallel in &mut self.parallel {
+            parallel.elapsed = time.clamp(0., parallel.animation.duration_secs());
         }
     }
 
-    pub fn init_background(&mut self, background: &mut BackgroundColor) {
-        if !self.init_background {
-            self.start.background_color = background.0;
-            self.init_background = true;
-        }
+    /// Whether the parallel animations reached their end, or their beginning backwards,
+    /// ones that repeat forever don't hold the sequence back.
+    fn parallel_done(&self, forward: bool) -> bool {
+        self.parallel.iter().all(|parallel| {
+            let length = parallel.animation.duration_secs();
+            if forward {
+                length.is_infinite() || parallel.elapsed >= length
+            } else {
+                parallel.elapsed <= 0.
+            }
+        })
     }
 
-    pub fn init_transform(&mut self, transform: &mut Transform) {
-        if !self.init_transform {
-            self.start.transform_translation = transform.translation;
-            self.start.transform_rotation = transform.rotation;
-            self.start.transform_scale = transform.scale;
-            self.init_transform = true;
+    /// Reached the end of the current [`Animation`] while playing forwards.
+    fn step_end(&mut self) {
+        let len = self.animations.len();
+        if self.exec_step {
+            return;
+        }
+        if self.exec_loop {
+            let next = (self.exec_index + 1) % len;
+            self.advance(next, true);
+            if next == 0 {
+                self.seek_parallel(0.);
+                self.events.push(AnimatorEventKind::LoopWrapped);
+            }
+        } else if self.exec_index + 1 == len {
+            if self.parallel_done(true) {
+                self.finish();
+            }
+        } else {
+            self.advance(self.exec_index + 1, true);
         }
     }
 
-    pub fn init_sprite(&mut self, sprite: &mut Sprite) {
-        if !self.init_sprite {
-            if let Some(size) = sprite.custom_size {
-                self.start.sprite_width = size.x;
-                self.start.sprite_height = size.y;
+    /// Reached the beginning of the current [`Animation`] while playing backwards.
+    fn step_begin(&mut self) {
+        let len = self.animations.len();
+        if self.exec_step {
+            return;
+        }
+        if self.exec_loop {
+            let prev = (self.exec_index + len - 1) % len;
+            let wrapped = self.exec_index == 0;
+            self.advance(prev, false);
+            self.seek_step_end();
+            if wrapped {
+                self.seek_parallel(self.step_offset(len));
+                self.events.push(AnimatorEventKind::LoopWrapped);
+            }
+        } else if self.exec_index == 0 {
+            if self.parallel_done(false) {
+                self.finish();
             }
-            self.start.sprite_color = sprite.color;
-            self.init_sprite = true;
-        }
-    }
-
-    pub fn tick_style(&mut self, style: &mut Style) {
-        self.init_style(style);
-        if let Some(ani) = self.animations.get(self.exec_index) {
-            self.start.lerp_style(
-                style,
-                &ani.change,
-                ani.ease_method.tick(if self.forward {
-                    self.fract
-                } else {
-                    1. - self.fract
-                }),
-            );
-        }
-    }
-
-    pub fn tick_text(&mut self, text: &mut Text) {
-        self.init_text(text);
-        if let Some(ani) = self.animations.get(self.exec_index) {
-            self.start.lerp_text(
-                text,
-                &ani.change,
-                ani.ease_method.tick(if self.forward {
-                    self.fract
-                } else {
-                    1. - self.fract
-                }),
-            );
-        }
-    }
-
-    pub fn tick_background(&mut self, background: &mut BackgroundColor) {
-        self.init_background(background);
-        if let Some(ani) = self.animations.get(self.exec_index) {
-            self.start.lerp_background(
-                background,
-                &ani.change,
-                ani.ease_method.tick(if self.forward {
-                    self.fract
-                } else {
-                    1. - self.fract
-                }),
-            );
-        }
-    }
-
-    pub fn tick_transform(&mut self, transform: &mut Transform) {
-        self.init_transform(transform);
-        if let Some(ani) = self.animations.get(self.exec_index) {
-            self.start.lerp_transform(
-                transform,
-                &ani.change,
-                ani.ease_method.tick(if self.forward {
-                    self.fract
-                } else {
-                    1. - self.fract
-                }),
-            );
-        }
-    }
-
-    pub fn tick_sprite(&mut self, sprite: &mut Sprite) {
-        self.init_sprite(sprite);
-        if let Some(ani) = self.animations.get(self.exec_index) {
-            self.start.lerp_sprite(
-                sprite,
-                &ani.change,
-                ani.ease_method.tick(if self.forward {
-                    self.fract
-                } else {
-                    1. - self.fract
-                }),
-            );
+        } else {
+            self.advance(self.exec_index - 1, false);
+            self.seek_step_end();
         }
     }
-}
 
-#[derive(Default, Debug)]
-pub struct Animation {
-    per: Duration,
-    repeat: Repeat,
-    total_times: f32,
-    mirror: bool,
-    ease_method: EaseMethod,
-    change: Anim,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Anim {
-    style_width: Val,
-    style_height: Val,
-    style_left: Val,
-    style_right: Val,
-    style_top: Val,
-    style_bottom: Val,
-    text_font_size: f32,
-    text_font_color: Color,
-    background_color: Color,
-    transform_translation: Vec3,
-    transform_rotation: Quat,
-    transform_scale: Vec3,
-    sprite_width: f32,
-    sprite_height: f32,
-    sprite_color: Color,
-}
+    fn seek_step_end(&mut self) {
+        self.elapsed = self.animations[self.exec_index]
+            .duration_secs()
+            .min(f32::MAX);
+        self.update_progress();
+    }
 
-impl Default for Anim {
-    fn default() -> Self {
-        Self {
-            style_width: Val::Auto,
-            style_height: Val::Auto,
-            style_left: Val::Auto,
-            style_right: Val::Auto,
-            style_top: Val::Auto,
-            style_bottom: Val::Auto,
-            text_font_size: 0.,
-            text_font_color: Color::NONE,
-            background_color: Color::NONE,
-            transform_translation: Vec3::ZERO,
-            transform_rotation: Quat::from_vec4(Vec4::ZERO),
-            transform_scale: Vec3::ZERO,
-            sprite_width: 0.,
-            sprite_height: 0.,
-            sprite_color: Color::NONE,
+    fn finish(&mut self) {
+        if !self.finished {
+            self.finished = true;
+            self.events.push(AnimatorEventKind::Finished);
         }
     }
-}
 
-macro_rules! lerp_val {
-    ($target:expr, $self:expr, $field:ident, $ratio:expr) => {
-        match ($target.$field, $self.$field) {
-            (Val::Px(s), Val::Px(v)) => Val::Px(s + v * $ratio),
-            (Val::Percent(s), Val::Percent(v)) => Val::Percent(s + v * $ratio),
-            (Val::Vw(s), Val::Vw(v)) => Val::Vw(s + v * $ratio),
-            (Val::Vh(s), Val::Vh(v)) => Val::Vh(s + v * $ratio),
-            (Val::VMin(s), Val::VMin(v)) => Val::VMin(s + v * $ratio),
-            (Val::VMax(s), Val::VMax(v)) => Val::VMax(s + v * $ratio),
-            _ => $target.$field,
+    fn advance(&mut self, index: usize, forward: bool) {
+        let from = self.exec_index;
+        self.enter(index, forward);
+        if from != index {
+            self.events
+                .push(AnimatorEventKind::StepAdvanced { from, to: index });
         }
-    };
-}
+    }
 
-impl Anim {
-    fn lerp_style(&self, target: &mut Style, change: &Self, ratio: f32) {
-        if change.style_width != Val::Auto {
-            target.width = lerp_val!(self, change, style_width, ratio);
-        }
-        if change.style_height != Val::Auto {
-            target.height = lerp_val!(self, change, style_height, ratio);
-        }
-        if change.style_left != Val::Auto {
-            target.left = lerp_val!(self, change, style_left, ratio);
-        }
-        if change.style_right != Val::Auto {
-            target.right = lerp_val!(self, change, style_right, ratio);
-        }
-        if change.style_top != Val::Auto {
-            target.top = lerp_val!(self, change, style_top, ratio);
-        }
-        if change.style_bottom != Val::Auto {
-            target.bottom = lerp_val!(self, change, style_bottom, ratio);
-        }
+    /// Whether the current step has properties on the component type `C`.
+    pub fn animates<C: Component>(&self) -> bool {
+        self.animations
+            .get(self.exec_index)
+            .into_iter()
+            .chain(self.parallel.iter().map(|parallel| &parallel.animation))
+            .flat_map(|ani| &ani.tracks)
+            .any(|track| track.component() == TypeId::of::<C>())
     }
 
-    fn lerp_text(&self, target: &mut Text, change: &Self, ratio: f32) {
-        if let Some(section) = target.sections.get_mut(0) {
-            if change.text_font_size != 0. {
-                section.style.font_size = self.text_font_size + change.text_font_size * ratio;
-            }
-            if change.text_font_color != Color::NONE {
-                section.style.color = self.text_font_color + change.text_font_color * ratio;
+    /// Writes the current step's and parallel properties of `C` into `component`.
+    pub fn tick_component<C: Component>(&mut self, component: &mut C) {
+        let mut written = Vec::new();
+        let sources = std::iter::once(TrackSource::Sequence)
+            .chain((0..self.parallel.len()).map(TrackSource::Parallel));
+        for source in sources {
+            let Some(count) = self.animation(source).map(|ani| ani.tracks.len()) else {
+                continue;
+            };
+            for index in 0..count {
+                let track = &self.animation(source).unwrap().tracks[index];
+                if track.component() != TypeId::of::<C>() {
+                    continue;
+                }
+                let key = track.key();
+                let additive = written.contains(&key);
+                self.tick_track(source, index, component, additive);
+                if !additive {
+                    written.push(key);
+                }
             }
         }
     }
 
-    fn lerp_background(&self, target: &mut BackgroundColor, change: &Self, ratio: f32) {
-        if target.0 != Color::NONE {
-            target.0 = self.background_color + change.background_color * ratio;
+    fn animation(&self, source: TrackSource) -> Option<&Animation> {
+        match source {
+            TrackSource::Sequence => self.animations.get(self.exec_index),
+            TrackSource::Parallel(index) => self.parallel.get(index).map(|p| &p.animation),
         }
     }
 
-    fn lerp_transform(&self, target: &mut Transform, change: &Self, ratio: f32) {
-        if change.transform_translation != Vec3::ZERO {
-            target.translation = self.transform_translation + change.transform_translation * ratio;
+    /// Sources, indices, keys and shared paths of the reflected tracks playing right now.
+    pub(crate) fn reflect_tracks(&self) -> Vec<(TrackSource, usize, TrackKey, Arc<str>)> {
+        let sources = std::iter::once(TrackSource::Sequence)
+            .chain((0..self.parallel.len()).map(TrackSource::Parallel));
+        sources
+            .filter_map(|source| Some((source, self.animation(source)?)))
+            .flat_map(|(source, ani)| {
+                ani.tracks
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(index, track)| {
+                        let path = track.reflect_path()?.clone();
+                        Some((source, index, track.key(), path))
+                    })
+            })
+            .collect()
+    }
+
+    /// Writes one track into `target`, `additive` tracks add their offset to the value
+    /// another track already wrote this frame.
+    pub(crate) fn tick_track(
+        &mut self,
+        source: TrackSource,
+        index: usize,
+        target: &mut dyn Any,
+        additive: bool,
+    ) {
+        if self.awaiting_layout {
+            return;
         }
-        if change.transform_rotation != Quat::from_vec4(Vec4::ZERO) {
-            target.rotation = self
-                .transform_rotation
-                .slerp(change.transform_rotation + self.transform_rotation, ratio);
+        let (ani, progress) = match source {
+            TrackSource::Sequence => (self.animations.get(self.exec_index), self.progress),
+            TrackSource::Parallel(index) => match self.parallel.get(index) {
+                Some(parallel) => (
+                    Some(&parallel.animation),
+                    parallel.animation.progress_at(parallel.elapsed),
+                ),
+                None => (None, 0.),
+            },
+        };
+        let Some((ani, track)) = ani.and_then(|ani| Some((ani, ani.tracks.get(index)?))) else {
+            return;
+        };
+        let ratio = ani.track_ratio(track.as_ref(), ani.directed_at(progress));
+        let key = track.key();
+        let layout = self.layout.as_ref();
+        if !self.start.contains(&key) {
+            track.capture(target, &mut self.start);
+            if let Some(layout) = layout {
+                track.resolve(&mut self.start, layout);
+            }
         }
-        if change.transform_scale != Vec3::ZERO {
-            target.scale = self.transform_scale + change.transform_scale * ratio;
+        if additive {
+            track.add_offset(target, &self.start, ratio);
+            return;
         }
-    }
-
-    fn lerp_sprite(&self, target: &mut Sprite, change: &Self, ratio: f32) {
-        if change.sprite_width != 0. || change.sprite_height != 0. {
-            target.custom_size = Some(Vec2::new(
-                self.sprite_width + change.sprite_width * ratio,
-                self.sprite_height + change.sprite_height * ratio,
-            ))
+        if source == TrackSource::Sequence && self.begun.insert(key) {
+            if track.mode() == TrackMode::Absolute {
+                track.capture(target, &mut self.start);
+            }
+            if let Some(layout) = layout {
+                track.resolve(&mut self.start, layout);
+            }
+            let saved = self.saved.entry(self.exec_index).or_default();
+            track.copy_start(&self.start, saved);
         }
-        if change.sprite_color != Color::NONE {
-            target.color = self.sprite_color + change.sprite_color * ratio;
+        if source == TrackSource::Sequence && track.spring().is_some() {
+            self.reached.insert(key);
+            if track.settle(target, &self.start, &mut self.velocity, self.dt) {
+                self.settling.remove(&key);
+            } else {
+                self.settling.insert(key);
+            }
+            return;
+        }
+        let blending = source == TrackSource::Sequence && self.elapsed < self.blend;
+        if blending && !self.blend_from.contains(&key) {
+            track.capture(target, &mut self.blend_from);
+        }
+        track.apply(target, &self.start, ratio);
+        if blending {
+            track.blend(target, &self.blend_from, self.elapsed / self.blend);
         }
     }
 }
 
-impl Add for Anim {
-    type Output = Self;
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TrackSource {
+    /// The current step of the sequence.
+    Sequence,
+    /// An animation added with [`Animator::add_parallel`].
+    Parallel(usize),
+}
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            style_width: add_val(self.style_width, rhs.style_width),
-            style_height: add_val(self.style_height, rhs.style_height),
-            style_left: add_val(self.style_left, rhs.style_left),
-            style_right: add_val(self.style_right, rhs.style_right),
-            style_top: add_val(self.style_top, rhs.style_top),
-            style_bottom: add_val(self.style_bottom, rhs.style_bottom),
-            text_font_size: self.text_font_size + rhs.text_font_size,
-            text_font_color: self.text_font_color + rhs.text_font_color,
-            background_color: self.background_color + rhs.background_color,
-            transform_translation: self.transform_translation + rhs.transform_translation,
-            transform_rotation: self.transform_rotation + rhs.transform_rotation,
-            transform_scale: self.transform_scale + rhs.transform_scale,
-            sprite_width: self.sprite_width + rhs.sprite_width,
-            sprite_height: self.sprite_height + rhs.sprite_height,
-            sprite_color: self.sprite_color + rhs.sprite_color,
-        }
-    }
+#[derive(Debug, Default)]
+struct Parallel {
+    animation: Animation,
+    elapsed: f32,
 }
 
-impl Sub for Anim {
-    type Output = Self;
+/// Which way each repetition of an [`Animation`] runs, like CSS `animation-direction`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlayDirection {
+    /// Every repetition runs from start to end.
+    #[default]
+    Normal,
+    /// Every repetition runs from end to start.
+    Reverse,
+    /// Odd repetitions run forwards, even ones backwards.
+    Alternate,
+    /// Odd repetitions run backwards, even ones forwards.
+    AlternateReverse,
+}
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            style_width: sub_val(self.style_width, rhs.style_width),
-            style_height: sub_val(self.style_height, rhs.style_height),
-            style_left: sub_val(self.style_left, rhs.style_left),
-            style_right: sub_val(self.style_right, rhs.style_right),
-            style_top: sub_val(self.style_top, rhs.style_top),
-            style_bottom: sub_val(self.style_bottom, rhs.style_bottom),
-            text_font_size: self.text_font_size - rhs.text_font_size,
-            text_font_color: sub_color(self.text_font_color, rhs.text_font_color),
-            background_color: sub_color(self.background_color, rhs.background_color),
-            transform_translation: self.transform_translation - rhs.transform_translation,
-            transform_rotation: self.transform_rotation - rhs.transform_rotation,
-            transform_scale: self.transform_scale - rhs.transform_scale,
-            sprite_width: self.sprite_width - rhs.sprite_width,
-            sprite_height: self.sprite_height - rhs.sprite_height,
-            sprite_color: sub_color(self.sprite_color, rhs.sprite_color),
-        }
+#[derive(Debug)]
+pub struct Animation {
+    per: Duration,
+    delay: Duration,
+    hold: Duration,
+    repeat: Repeat,
+    total_times: f32,
+    direction: PlayDirection,
+    ease_method: EaseMethod,
+    tracks: Vec<Box<dyn Track>>,
+    last_group: usize,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        let mut animation = Self {
+            per: Duration::ZERO,
+            delay: Duration::ZERO,
+            hold: Duration::ZERO,
+            repeat: Repeat::default(),
+            total_times: 0.,
+            direction: PlayDirection::default(),
+            ease_method: EaseMethod::default(),
+            tracks: Vec::new(),
+            last_group: 0,
+        };
+        animation.total_times = animation.get_total_times();
+        animation
     }
 }
 
+fn to_duration(secs: f32) -> Duration {
+    Duration::try_from_secs_f32(secs).unwrap_or(Duration::MAX)
+}
+
 impl Animation {
+    /// Length of the whole step including delay and hold, infinite for [`Repeat::Infinite`].
+    fn duration_secs(&self) -> f32 {
+        let active = match self.repeat {
+            Repeat::Infinite => f32::INFINITY,
+            Repeat::Duration(duration) => duration.as_secs_f32(),
+            Repeat::Finite(count) => self.per.as_secs_f32() * count as f32,
+        };
+        self.delay.as_secs_f32() + active + self.hold.as_secs_f32()
+    }
+
     fn get_total_times(&self) -> f32 {
         match self.repeat {
             Repeat::Finite(count) => count as f32,
@@ -424,8 +770,97 @@ impl Animation {
         }
     }
 
+    /// Direction and fraction of the repetition `progress` falls in.
+    fn split_progress(&self, progress: f32) -> (bool, f32) {
+        let mut times = progress.trunc();
+        let mut fract = progress.fract();
+        if fract == 0. && times > 0. && progress >= self.total_times {
+            times -= 1.;
+            fract = 1.;
+        }
+        let even = times % 2. == 0.;
+        let forward = match self.direction {
+            PlayDirection::Normal => true,
+            PlayDirection::Reverse => false,
+            PlayDirection::Alternate => even,
+            PlayDirection::AlternateReverse => !even,
+        };
+        (forward, fract)
+    }
+
+    /// Moves `start` past the end of this step.
+    fn shift_to_end(&self, start: &mut StartMap) {
+        let end = self.directed_at(self.total_times);
+        for track in &self.tracks {
+            let ratio = self.track_ratio(track.as_ref(), end);
+            track.shift(start, ratio, false);
+        }
+    }
+
+    /// Moves `start` back before this step, taking the starts in `saved` where there are
+    /// any and undoing relative steps otherwise.
+    fn restore(&self, start: &mut StartMap, saved: Option<&StartMap>) {
+        let end = self.directed_at(self.total_times);
+        for track in &self.tracks {
+            match saved.filter(|saved| saved.contains(&track.key())) {
+                Some(saved) => track.copy_start(saved, start),
+                None => track.shift(start, self.track_ratio(track.as_ref(), end), true),
+            }
+        }
+    }
+
+    /// Copies the starts of this step's properties into `saved`.
+    fn save(&self, start: &StartMap, saved: &mut StartMap) {
+        for track in &self.tracks {
+            track.copy_start(start, saved);
+        }
+    }
+
+    /// Repetitions played `elapsed` seconds into the step, steps without a duration
+    /// jump to their end once the delay passed.
+    fn progress_at(&self, elapsed: f32) -> f32 {
+        let active = elapsed - self.delay.as_secs_f32();
+        if self.per.is_zero() && active >= 0. {
+            self.total_times
+        } else if active <= 0. {
+            0.
+        } else {
+            (active / self.per.as_secs_f32()).min(self.total_times)
+        }
+    }
+
+    /// Uneased ratio of the properties after `progress` repetitions.
+    fn directed_at(&self, progress: f32) -> f32 {
+        let (forward, fract) = self.split_progress(progress);
+        if forward {
+            fract
+        } else {
+            1. - fract
+        }
+    }
+
+    /// Eases `ratio` with the track's own [`EaseMethod`], falling back to the step's.
+    fn track_ratio(&self, track: &dyn Track, ratio: f32) -> f32 {
+        track.ease().unwrap_or(&self.ease_method).tick(ratio)
+    }
+
+    /// Makes every other repetition run backwards, keeping the current start direction.
     pub fn set_mirror(&mut self, mirror: bool) -> &mut Self {
-        self.mirror = mirror;
+        let reversed = matches!(
+            self.direction,
+            PlayDirection::Reverse | PlayDirection::AlternateReverse
+        );
+        self.direction = match (mirror, reversed) {
+            (false, false) => PlayDirection::Normal,
+            (false, true) => PlayDirection::Reverse,
+            (true, false) => PlayDirection::Alternate,
+            (true, true) => PlayDirection::AlternateReverse,
+        };
+        self
+    }
+
+    pub fn set_direction(&mut self, direction: PlayDirection) -> &mut Self {
+        self.direction = direction;
         self
     }
 
@@ -442,12 +877,27 @@ impl Animation {
         self
     }
 
-    pub fn set_delay(&mut self, delay: Duration) -> &mut Self {
-        self.per = delay;
+    /// Length of a single repetition.
+    pub fn set_duration(&mut self, duration: Duration) -> &mut Self {
+        self.per = duration;
         self.total_times = self.get_total_times();
         self
     }
 
+    /// Time to wait at the start value before the first repetition begins.
+    ///
+    /// This used to set the length of a repetition, which is [`Animation::set_duration`] now.
+    pub fn set_delay(&mut self, delay: Duration) -> &mut Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Time to keep the end value after the last repetition before the next step.
+    pub fn set_hold(&mut self, hold: Duration) -> &mut Self {
+        self.hold = hold;
+        self
+    }
+
     pub fn set_repeat(&mut self, repeat: Repeat) -> &mut Self {
         self.repeat = repeat;
         self.total_times = self.get_total_times();
@@ -459,111 +909,498 @@ impl Animation {
         self
     }
 
-    pub fn set_wh(&mut self, width: Val, height: Val) -> &mut Self {
-        self.change.style_width = width;
-        self.change.style_height = height;
+    fn push_track(&mut self, track: Box<dyn Track>) -> &mut Self {
+        let key = track.key();
+        self.tracks.retain(|other| other.key() != key);
+        self.tracks.push(track);
+        self.last_group = 1;
         self
     }
 
-    pub fn set_width(&mut self, width: Val) -> &mut Self {
-        self.change.style_width = width;
+    /// Lets [`Animation::with_ease`] cover the last `len` properties set together.
+    fn group(&mut self, len: usize) -> &mut Self {
+        self.last_group = len;
         self
     }
 
-    pub fn set_height(&mut self, height: Val) -> &mut Self {
-        self.change.style_height = height;
+    /// Eases the properties of the previous setter with `ease_method` instead of the
+    /// step's [`Animation::set_ease`], e.g. `.set_translation(v).with_ease(back_out)`.
+    pub fn with_ease(&mut self, ease_method: EaseMethod) -> &mut Self {
+        for track in self.tracks.iter_mut().rev().take(self.last_group) {
+            track.set_ease(ease_method.clone());
+        }
         self
     }
 
-    pub fn set_lt(&mut self, left: Val, top: Val) -> &mut Self {
-        self.change.style_left = left;
-        self.change.style_top = top;
+    /// Interpolates the colors of the previous setter in `space` instead of raw sRGB.
+    pub fn with_color_space(&mut self, space: ColorSpace) -> &mut Self {
+        for track in self.tracks.iter_mut().rev().take(self.last_group) {
+            track.set_color_space(space);
+        }
         self
     }
 
-    pub fn set_left(&mut self, left: Val) -> &mut Self {
-        self.change.style_left = left;
+    /// Drives the properties of the previous setter with `spring` instead of the step's
+    /// timing, the step then also waits for them to come to rest.
+    ///
+    /// Only steps of the sequence spring, parallel animations keep their eased timing.
+    pub fn with_spring(&mut self, spring: Spring) -> &mut Self {
+        for track in self.tracks.iter_mut().rev().take(self.last_group) {
+            track.set_spring(spring);
+        }
         self
     }
 
+    /// Animates the property behind `lens` by `delta` from the start of the step.
+    pub fn set<L: Lens>(&mut self, lens: L, delta: L::Value) -> &mut Self {
+        self.push_track(Box::new(PropertyTrack {
+            lens,
+            curve: Curve::Value(delta),
+            mode: TrackMode::Relative,
+            ease: None,
+            spring: None,
+            space: ColorSpace::default(),
+        }))
+    }
+
+    /// Animates the property behind `lens` to `target` from its value when the step begins.
+    pub fn to<L: Lens>(&mut self, lens: L, target: L::Value) -> &mut Self {
+        self.push_track(Box::new(PropertyTrack {
+            lens,
+            curve: Curve::Value(target),
+            mode: TrackMode::Absolute,
+            ease: None,
+            spring: None,
+            space: ColorSpace::default(),
+        }))
+    }
+
+    /// Moves the property behind `lens` through deltas from the start of the step.
+    pub fn set_keyframes<L: Lens>(&mut self, lens: L, keyframes: Keyframes<L::Value>) -> &mut Self {
+        self.push_track(Box::new(PropertyTrack {
+            lens,
+            curve: Curve::Keyframes(keyframes),
+            mode: TrackMode::Relative,
+            ease: None,
+            spring: None,
+            space: ColorSpace::default(),
+        }))
+    }
+
+    /// Moves the property behind `lens` through absolute values, starting from its value
+    /// when the step begins.
+    pub fn to_keyframes<L: Lens>(&mut self, lens: L, keyframes: Keyframes<L::Value>) -> &mut Self {
+        self.push_track(Box::new(PropertyTrack {
+            lens,
+            curve: Curve::Keyframes(keyframes),
+            mode: TrackMode::Absolute,
+            ease: None,
+            spring: None,
+            space: ColorSpace::default(),
+        }))
+    }
+
+    /// Animates a reflected field such as `"Style::margin.left"` by `delta`, the component
+    /// has to be registered with `#[reflect(Component)]`.
+    pub fn set_path(
+        &mut self,
+        path: impl Into<String>,
+        delta: impl Into<ReflectValue>,
+    ) -> &mut Self {
+        self.push_track(Box::new(ReflectTrack {
+            path: path.into().into(),
+            curve: Curve::Value(delta.into()),
+            mode: TrackMode::Relative,
+            ease: None,
+            spring: None,
+            space: ColorSpace::default(),
+        }))
+    }
+
+    /// Animates a reflected field to `target` from its value when the step begins.
+    pub fn to_path(
+        &mut self,
+        path: impl Into<String>,
+        target: impl Into<ReflectValue>,
+    ) -> &mut Self {
+        self.push_track(Box::new(ReflectTrack {
+            path: path.into().into(),
+            curve: Curve::Value(target.into()),
+            mode: TrackMode::Absolute,
+            ease: None,
+            spring: None,
+            space: ColorSpace::default(),
+        }))
+    }
+
+    pub fn set_wh(&mut self, width: Val, height: Val) -> &mut Self {
+        self.set(StyleWidthLens, width)
+            .set(StyleHeightLens, height)
+            .group(2)
+    }
+
+    pub fn set_width(&mut self, width: Val) -> &mut Self {
+        self.set(StyleWidthLens, width)
+    }
+
+    pub fn set_height(&mut self, height: Val) -> &mut Self {
+        self.set(StyleHeightLens, height)
+    }
+
+    pub fn set_lt(&mut self, left: Val, top: Val) -> &mut Self {
+        self.set(StyleLeftLens, left)
+            .set(StyleTopLens, top)
+            .group(2)
+    }
+
+    pub fn set_left(&mut self, left: Val) -> &mut Self {
+        self.set(StyleLeftLens, left)
+    }
+
     pub fn set_top(&mut self, top: Val) -> &mut Self {
-        self.change.style_top = top;
-        self
+        self.set(StyleTopLens, top)
     }
 
     pub fn set_rb(&mut self, right: Val, bottom: Val) -> &mut Self {
-        self.change.style_right = right;
-        self.change.style_bottom = bottom;
-        self
+        self.set(StyleRightLens, right)
+            .set(StyleBottomLens, bottom)
+            .group(2)
     }
 
     pub fn set_right(&mut self, right: Val) -> &mut Self {
-        self.change.style_right = right;
-        self
+        self.set(StyleRightLens, right)
     }
 
     pub fn set_bottom(&mut self, bottom: Val) -> &mut Self {
-        self.change.style_bottom = bottom;
-        self
+        self.set(StyleBottomLens, bottom)
+    }
+
+    pub fn set_min_width(&mut self, min_width: Val) -> &mut Self {
+        self.set(StyleMinWidthLens, min_width)
+    }
+
+    pub fn set_min_height(&mut self, min_height: Val) -> &mut Self {
+        self.set(StyleMinHeightLens, min_height)
+    }
+
+    pub fn set_max_width(&mut self, max_width: Val) -> &mut Self {
+        self.set(StyleMaxWidthLens, max_width)
+    }
+
+    pub fn set_max_height(&mut self, max_height: Val) -> &mut Self {
+        self.set(StyleMaxHeightLens, max_height)
+    }
+
+    pub fn set_margin(&mut self, margin: UiRect) -> &mut Self {
+        self.set(StyleMarginLens, margin)
+    }
+
+    pub fn set_padding(&mut self, padding: UiRect) -> &mut Self {
+        self.set(StylePaddingLens, padding)
+    }
+
+    pub fn set_border(&mut self, border: UiRect) -> &mut Self {
+        self.set(StyleBorderLens, border)
+    }
+
+    pub fn set_gap(&mut self, column_gap: Val, row_gap: Val) -> &mut Self {
+        self.set(StyleColumnGapLens, column_gap)
+            .set(StyleRowGapLens, row_gap)
+            .group(2)
+    }
+
+    pub fn set_column_gap(&mut self, column_gap: Val) -> &mut Self {
+        self.set(StyleColumnGapLens, column_gap)
+    }
+
+    pub fn set_row_gap(&mut self, row_gap: Val) -> &mut Self {
+        self.set(StyleRowGapLens, row_gap)
+    }
+
+    pub fn set_flex_basis(&mut self, flex_basis: Val) -> &mut Self {
+        self.set(StyleFlexBasisLens, flex_basis)
+    }
+
+    pub fn set_flex_grow(&mut self, flex_grow: f32) -> &mut Self {
+        self.set(StyleFlexGrowLens, flex_grow)
     }
 
     pub fn set_text(&mut self, font_size: f32, font_color: Color) -> &mut Self {
-        self.change.text_font_size = font_size;
-        self.change.text_font_color = font_color;
-        self
+        self.set(TextFontSizeLens(0), font_size)
+            .set(TextColorLens(0), font_color)
+            .group(2)
     }
 
     pub fn set_background(&mut self, color: Color) -> &mut Self {
-        self.change.background_color = color;
-        self
+        self.set(BackgroundColorLens, color)
+    }
+
+    /// Tints the image of a `UiImage` node.
+    pub fn set_image_tint(&mut self, color: Color) -> &mut Self {
+        self.set(ImageTintLens, color)
+    }
+
+    pub fn set_border_color(&mut self, color: Color) -> &mut Self {
+        self.set(BorderColorLens, color)
+    }
+
+    pub fn set_outline(&mut self, color: Color, width: Val, offset: Val) -> &mut Self {
+        self.set(OutlineColorLens, color)
+            .set(OutlineWidthLens, width)
+            .set(OutlineOffsetLens, offset)
+            .group(3)
+    }
+
+    pub fn set_outline_color(&mut self, color: Color) -> &mut Self {
+        self.set(OutlineColorLens, color)
     }
 
     pub fn set_transform(&mut self, translation: Vec3, rotation: Quat, scale: Vec3) -> &mut Self {
-        self.change.transform_translation = translation;
-        self.change.transform_rotation = rotation;
-        self.change.transform_scale = scale;
-        self
+        self.set(TransformTranslationLens, translation)
+            .set(TransformRotationLens, rotation)
+            .set(TransformScaleLens, scale)
+            .group(3)
     }
 
     pub fn set_translation(&mut self, translation: Vec3) -> &mut Self {
-        self.change.transform_translation = translation;
-        self
+        self.set(TransformTranslationLens, translation)
     }
 
+    /// Composes `rotation` onto the start of the step along the shortest arc, use
+    /// [`Animation::set_rotation_axis`] for spins of half a turn or more.
     pub fn set_rotation(&mut self, rotation: Quat) -> &mut Self {
-        self.change.transform_rotation = rotation;
-        self
+        self.set(TransformRotationLens, rotation)
+    }
+
+    /// Turns by Euler angles in degrees from the start of the step, beyond 360° for spins.
+    pub fn set_rotation_euler(&mut self, degrees: Vec3) -> &mut Self {
+        self.set(TransformEulerLens::default(), degrees)
+    }
+
+    /// Turns around the local `axis` by `degrees`, any amount of full turns included.
+    pub fn set_rotation_axis(&mut self, axis: Vec3, degrees: f32) -> &mut Self {
+        self.set(TransformAxisAngleLens { axis }, degrees)
     }
 
     pub fn set_scale(&mut self, scale: Vec3) -> &mut Self {
-        self.change.transform_scale = scale;
-        self
+        self.set(TransformScaleLens, scale)
     }
 
     pub fn set_sprite(&mut self, width: f32, height: f32, color: Color) -> &mut Self {
-        self.change.sprite_width = width;
-        self.change.sprite_height = height;
-        self.change.sprite_color = color;
-        self
+        self.set(SpriteSizeLens, Vec2::new(width, height))
+            .set(SpriteColorLens, color)
+            .group(2)
     }
 
     pub fn set_sprite_size(&mut self, width: f32, height: f32) -> &mut Self {
-        self.change.sprite_width = width;
-        self.change.sprite_height = height;
-        self
+        self.set(SpriteSizeLens, Vec2::new(width, height))
     }
 
     pub fn set_sprite_color(&mut self, color: Color) -> &mut Self {
-        self.change.sprite_color = color;
-        self
+        self.set(SpriteColorLens, color)
+    }
+
+    /// Changes the [`Opacity`](crate::opacity::Opacity) faded into the whole hierarchy.
+    pub fn set_opacity(&mut self, opacity: f32) -> &mut Self {
+        self.set(OpacityLens, opacity)
+    }
+
+    pub fn to_wh(&mut self, width: Val, height: Val) -> &mut Self {
+        self.to(StyleWidthLens, width)
+            .to(StyleHeightLens, height)
+            .group(2)
+    }
+
+    pub fn to_width(&mut self, width: Val) -> &mut Self {
+        self.to(StyleWidthLens, width)
+    }
+
+    pub fn to_height(&mut self, height: Val) -> &mut Self {
+        self.to(StyleHeightLens, height)
+    }
+
+    pub fn to_lt(&mut self, left: Val, top: Val) -> &mut Self {
+        self.to(StyleLeftLens, left).to(StyleTopLens, top).group(2)
+    }
+
+    pub fn to_left(&mut self, left: Val) -> &mut Self {
+        self.to(StyleLeftLens, left)
+    }
+
+    pub fn to_top(&mut self, top: Val) -> &mut Self {
+        self.to(StyleTopLens, top)
+    }
+
+    pub fn to_rb(&mut self, right: Val, bottom: Val) -> &mut Self {
+        self.to(StyleRightLens, right)
+            .to(StyleBottomLens, bottom)
+            .group(2)
+    }
+
+    pub fn to_right(&mut self, right: Val) -> &mut Self {
+        self.to(StyleRightLens, right)
+    }
+
+    pub fn to_bottom(&mut self, bottom: Val) -> &mut Self {
+        self.to(StyleBottomLens, bottom)
+    }
+
+    pub fn to_min_width(&mut self, min_width: Val) -> &mut Self {
+        self.to(StyleMinWidthLens, min_width)
+    }
+
+    pub fn to_min_height(&mut self, min_height: Val) -> &mut Self {
+        self.to(StyleMinHeightLens, min_height)
+    }
+
+    pub fn to_max_width(&mut self, max_width: Val) -> &mut Self {
+        self.to(StyleMaxWidthLens, max_width)
+    }
+
+    pub fn to_max_height(&mut self, max_height: Val) -> &mut Self {
+        self.to(StyleMaxHeightLens, max_height)
+    }
+
+    pub fn to_margin(&mut self, margin: UiRect) -> &mut Self {
+        self.to(StyleMarginLens, margin)
+    }
+
+    pub fn to_padding(&mut self, padding: UiRect) -> &mut Self {
+        self.to(StylePaddingLens, padding)
+    }
+
+    pub fn to_border(&mut self, border: UiRect) -> &mut Self {
+        self.to(StyleBorderLens, border)
+    }
+
+    pub fn to_gap(&mut self, column_gap: Val, row_gap: Val) -> &mut Self {
+        self.to(StyleColumnGapLens, column_gap)
+            .to(StyleRowGapLens, row_gap)
+            .group(2)
+    }
+
+    pub fn to_column_gap(&mut self, column_gap: Val) -> &mut Self {
+        self.to(StyleColumnGapLens, column_gap)
+    }
+
+    pub fn to_row_gap(&mut self, row_gap: Val) -> &mut Self {
+        self.to(StyleRowGapLens, row_gap)
+    }
+
+    pub fn to_flex_basis(&mut self, flex_basis: Val) -> &mut Self {
+        self.to(StyleFlexBasisLens, flex_basis)
+    }
+
+    pub fn to_flex_grow(&mut self, flex_grow: f32) -> &mut Self {
+        self.to(StyleFlexGrowLens, flex_grow)
+    }
+
+    pub fn to_text(&mut self, font_size: f32, font_color: Color) -> &mut Self {
+        self.to(TextFontSizeLens(0), font_size)
+            .to(TextColorLens(0), font_color)
+            .group(2)
+    }
+
+    pub fn to_background(&mut self, color: Color) -> &mut Self {
+        self.to(BackgroundColorLens, color)
+    }
+
+    /// Tints the image of a `UiImage` node.
+    pub fn to_image_tint(&mut self, color: Color) -> &mut Self {
+        self.to(ImageTintLens, color)
+    }
+
+    pub fn to_border_color(&mut self, color: Color) -> &mut Self {
+        self.to(BorderColorLens, color)
+    }
+
+    pub fn to_outline(&mut self, color: Color, width: Val, offset: Val) -> &mut Self {
+        self.to(OutlineColorLens, color)
+            .to(OutlineWidthLens, width)
+            .to(OutlineOffsetLens, offset)
+            .group(3)
+    }
+
+    pub fn to_outline_color(&mut self, color: Color) -> &mut Self {
+        self.to(OutlineColorLens, color)
+    }
+
+    pub fn to_transform(&mut self, translation: Vec3, rotation: Quat, scale: Vec3) -> &mut Self {
+        self.to(TransformTranslationLens, translation)
+            .to(TransformRotationLens, rotation)
+            .to(TransformScaleLens, scale)
+            .group(3)
+    }
+
+    pub fn to_translation(&mut self, translation: Vec3) -> &mut Self {
+        self.to(TransformTranslationLens, translation)
+    }
+
+    /// Slerps to the orientation `rotation` along the shortest arc.
+    pub fn to_rotation(&mut self, rotation: Quat) -> &mut Self {
+        self.to(TransformRotationLens, rotation)
+    }
+
+    /// Turns to Euler angles in degrees, values past 360° spin that many extra turns.
+    pub fn to_rotation_euler(&mut self, degrees: Vec3) -> &mut Self {
+        self.to(TransformEulerLens::default(), degrees)
+    }
+
+    /// Turns around the local `axis` until the angle around it reaches `degrees`.
+    pub fn to_rotation_axis(&mut self, axis: Vec3, degrees: f32) -> &mut Self {
+        self.to(TransformAxisAngleLens { axis }, degrees)
+    }
+
+    pub fn to_scale(&mut self, scale: Vec3) -> &mut Self {
+        self.to(TransformScaleLens, scale)
+    }
+
+    pub fn to_sprite(&mut self, width: f32, height: f32, color: Color) -> &mut Self {
+        self.to(SpriteSizeLens, Vec2::new(width, height))
+            .to(SpriteColorLens, color)
+            .group(2)
+    }
+
+    pub fn to_sprite_size(&mut self, width: f32, height: f32) -> &mut Self {
+        self.to(SpriteSizeLens, Vec2::new(width, height))
+    }
+
+    pub fn to_sprite_color(&mut self, color: Color) -> &mut Self {
+        self.to(SpriteColorLens, color)
+    }
+
+    pub fn to_opacity(&mut self, opacity: f32) -> &mut Self {
+        self.to(OpacityLens, opacity)
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub enum EaseMethod {
     EaseFun(EaseFunction),
     Linear,
     Custom(fn(f32) -> f32),
+    /// A closure that can capture its parameters, see [`EaseMethod::closure`].
+    Closure(Arc<dyn Fn(f32) -> f32 + Send + Sync>),
+    /// Evenly spaced samples from `0` to `1`, linearly interpolated in between.
+    Sampled(Arc<[f32]>),
+    /// CSS `cubic-bezier(x1, y1, x2, y2)`, `x1` and `x2` are clamped to `0.0..=1.0`.
+    CubicBezier(f32, f32, f32, f32),
+    /// CSS `steps(n, position)`.
+    Steps(u16, StepPosition),
+}
+
+/// Where the jumps of [`EaseMethod::Steps`] happen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StepPosition {
+    /// Jumps at the start of every interval, the first value is already one step in.
+    JumpStart,
+    /// Jumps at the end of every interval, the last value is only reached at the end.
+    #[default]
+    JumpEnd,
+    /// Jumps at both ends, neither the first nor the last interval rests on `0` or `1`.
+    JumpBoth,
+    /// Holds `0` during the first interval and `1` during the last.
+    JumpNone,
 }
 
 impl Default for EaseMethod {
@@ -572,14 +1409,288 @@ impl Default for EaseMethod {
     }
 }
 
+impl fmt::Debug for EaseMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EaseMethod::EaseFun(fun) => f.debug_tuple("EaseFun").field(fun).finish(),
+            EaseMethod::Linear => f.write_str("Linear"),
+            EaseMethod::Custom(fun) => f.debug_tuple("Custom").field(fun).finish(),
+            EaseMethod::Closure(_) => f.write_str("Closure(..)"),
+            EaseMethod::Sampled(samples) => f
+                .debug_struct("Sampled")
+                .field("len", &samples.len())
+                .finish(),
+            EaseMethod::CubicBezier(x1, y1, x2, y2) => f
+                .debug_tuple("CubicBezier")
+                .field(x1)
+                .field(y1)
+                .field(x2)
+                .field(y2)
+                .finish(),
+            EaseMethod::Steps(steps, position) => {
+                f.debug_tuple("Steps").field(steps).field(position).finish()
+            }
+        }
+    }
+}
+
 impl EaseMethod {
+    pub fn closure(fun: impl Fn(f32) -> f32 + Send + Sync + 'static) -> Self {
+        EaseMethod::Closure(Arc::new(fun))
+    }
+
+    /// Samples `fun` at `count` evenly spaced points so it can be cloned and ticked cheaply.
+    pub fn sample(count: usize, fun: impl Fn(f32) -> f32) -> Self {
+        let last = count.max(2) - 1;
+        EaseMethod::Sampled(
+            (0..=last)
+                .map(|index| fun(index as f32 / last as f32))
+                .collect(),
+        )
+    }
+
+    /// Elastic ease in, `amplitude` below `1` is raised to `1`, `period` is a fraction
+    /// of the whole duration.
+    pub fn elastic_in(amplitude: f32, period: f32) -> Self {
+        Self::closure(move |t| 1. - elastic_out(amplitude, period, 1. - t))
+    }
+
+    pub fn elastic_out(amplitude: f32, period: f32) -> Self {
+        Self::closure(move |t| elastic_out(amplitude, period, t))
+    }
+
+    /// Back ease in pulling `overshoot` away before moving, `1.70158` is the classic amount.
+    pub fn back_in(overshoot: f32) -> Self {
+        Self::closure(move |t| t * t * ((overshoot + 1.) * t - overshoot))
+    }
+
+    pub fn back_out(overshoot: f32) -> Self {
+        Self::closure(move |t| {
+            let t = t - 1.;
+            1. + t * t * ((overshoot + 1.) * t + overshoot)
+        })
+    }
+
     pub fn tick(&self, tick: f32) -> f32 {
         match self {
             EaseMethod::EaseFun(fun) => tick.calc(*fun),
             EaseMethod::Linear => tick,
             EaseMethod::Custom(fun) => fun(tick),
+            EaseMethod::Closure(fun) => fun(tick),
+            EaseMethod::Sampled(samples) => sampled(samples, tick),
+            EaseMethod::CubicBezier(x1, y1, x2, y2) => cubic_bezier(*x1, *y1, *x2, *y2, tick),
+            EaseMethod::Steps(steps, position) => ease_steps(*steps, *position, tick),
+        }
+    }
+}
+
+/// Damped harmonic oscillator moving a property toward the end value of its step.
+///
+/// The velocity a property has when its step changes carries over to the next
+/// spring on the same property, so interrupted motion stays continuous.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spring {
+    pub stiffness: f32,
+    pub damping: f32,
+    pub mass: f32,
+    /// Starting velocity as a fraction of the distance to the target per second,
+    /// used when the property isn't already moving.
+    pub velocity: f32,
+    /// Distance to the target and speed below which the property snaps to rest,
+    /// in the units of the property.
+    pub rest: f32,
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self {
+            stiffness: 170.,
+            damping: 26.,
+            mass: 1.,
+            velocity: 0.,
+            rest: 0.01,
+        }
+    }
+}
+
+impl Spring {
+    /// Longest time step integrated at once, smaller steps keep stiff springs stable.
+    const SUBSTEP: f32 = 1. / 240.;
+
+    pub fn new(stiffness: f32, damping: f32) -> Self {
+        Self {
+            stiffness,
+            damping,
+            ..default()
+        }
+    }
+
+    /// Advances `position` toward `target` by `dt` seconds, keeping the velocity of `key`
+    /// in `velocity`, returns whether it came to rest.
+    pub(crate) fn settle<V: Animatable>(
+        &self,
+        position: &mut V,
+        mut target: V,
+        velocity: &mut StartMap,
+        key: TrackKey,
+        dt: f32,
+    ) -> bool {
+        if let (Some(rotation), Some(&mut to)) = (position.rotation_mut(), target.rotation_mut()) {
+            return self.settle_rotation(rotation, to, velocity, key, dt);
+        }
+        let mass = self.mass.max(f32::EPSILON);
+        let mut speed = velocity
+            .get::<V>(&key)
+            .unwrap_or_else(|| target.sub(*position).scale(self.velocity));
+        let mut left = dt;
+        while left > 0. {
+            let step = left.min(Self::SUBSTEP);
+            let force = position
+                .sub(target)
+                .scale(-self.stiffness)
+                .add(speed.scale(-self.damping));
+            speed = speed.add(force.scale(step / mass));
+            *position = position.add(speed.scale(step));
+            left -= step;
+        }
+        let rest = position.sub(target).norm() < self.rest && speed.norm() < self.rest;
+        if rest {
+            *position = target;
+            velocity.remove(&key);
+        } else {
+            velocity.insert(key, speed);
+        }
+        rest
+    }
+
+    /// Springs a rotation with its offset and angular velocity as scaled axis vectors,
+    /// so it turns along the shortest arc and overshoots past the target.
+    fn settle_rotation(
+        &self,
+        rotation: &mut Quat,
+        target: Quat,
+        velocity: &mut StartMap,
+        key: TrackKey,
+        dt: f32,
+    ) -> bool {
+        let offset = |rotation: Quat| {
+            let offset = rotation * target.inverse();
+            let offset = if offset.w < 0. { -offset } else { offset };
+            offset.to_scaled_axis()
+        };
+        let mass = self.mass.max(f32::EPSILON);
+        let mut speed = velocity
+            .get::<Vec3>(&key)
+            .unwrap_or_else(|| offset(*rotation) * -self.velocity);
+        let mut left = dt;
+        while left > 0. {
+            let step = left.min(Self::SUBSTEP);
+            let force = offset(*rotation) * -self.stiffness - speed * self.damping;
+            speed += force * (step / mass);
+            *rotation = (Quat::from_scaled_axis(speed * step) * *rotation).normalize();
+            left -= step;
+        }
+        let rest = offset(*rotation).length() < self.rest && speed.length() < self.rest;
+        if rest {
+            *rotation = target;
+            velocity.remove(&key);
+        } else {
+            velocity.insert(key, speed);
+        }
+        rest
+    }
+}
+
+fn elastic_out(amplitude: f32, period: f32, t: f32) -> f32 {
+    if t <= 0. || t >= 1. {
+        return t.clamp(0., 1.);
+    }
+    let amplitude = amplitude.max(1.);
+    let period = period.max(f32::EPSILON);
+    let shift = period / TAU * (1. / amplitude).asin();
+    amplitude * 2f32.powf(-10. * t) * ((t - shift) * TAU / period).sin() + 1.
+}
+
+fn sampled(samples: &[f32], t: f32) -> f32 {
+    match samples {
+        [] => t,
+        [only] => *only,
+        _ => {
+            let position = t.clamp(0., 1.) * (samples.len() - 1) as f32;
+            let index = (position as usize).min(samples.len() - 2);
+            let (from, to) = (samples[index], samples[index + 1]);
+            from + (to - from) * (position - index as f32)
+        }
+    }
+}
+
+/// One coordinate of a bezier curve from `(0, 0)` to `(1, 1)` at parameter `t`.
+fn bezier(p1: f32, p2: f32, t: f32) -> f32 {
+    let u = 1. - t;
+    3. * u * u * t * p1 + 3. * u * t * t * p2 + t * t * t
+}
+
+fn bezier_slope(p1: f32, p2: f32, t: f32) -> f32 {
+    let u = 1. - t;
+    3. * u * u * p1 + 6. * u * t * (p2 - p1) + 3. * t * t * (1. - p2)
+}
+
+/// Solves the curve's parameter for `x` with Newton's method, falling back to
+/// bisection where the slope is too flat, then reads `y` there.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    const EPSILON: f32 = 1e-6;
+    let (x1, x2) = (x1.clamp(0., 1.), x2.clamp(0., 1.));
+    if x <= 0. || x >= 1. {
+        return x.clamp(0., 1.);
+    }
+    let mut t = x;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, t) - x;
+        if error.abs() < EPSILON {
+            return bezier(y1, y2, t);
         }
+        let slope = bezier_slope(x1, x2, t);
+        if slope.abs() < EPSILON {
+            break;
+        }
+        t -= error / slope;
+    }
+    let (mut low, mut high) = (0., 1.);
+    t = x;
+    for _ in 0..32 {
+        let error = bezier(x1, x2, t) - x;
+        if error.abs() < EPSILON {
+            break;
+        }
+        if error > 0. {
+            high = t;
+        } else {
+            low = t;
+        }
+        t = (low + high) / 2.;
+    }
+    bezier(y1, y2, t)
+}
+
+/// Follows the CSS step easing algorithm.
+fn ease_steps(steps: u16, position: StepPosition, x: f32) -> f32 {
+    let steps = steps.max(1) as f32;
+    let mut step = (x * steps).floor();
+    if matches!(position, StepPosition::JumpStart | StepPosition::JumpBoth) {
+        step += 1.;
     }
+    let jumps = match position {
+        StepPosition::JumpStart | StepPosition::JumpEnd => steps,
+        StepPosition::JumpBoth => steps + 1.,
+        StepPosition::JumpNone => (steps - 1.).max(1.),
+    };
+    if x >= 0. && step < 0. {
+        step = 0.;
+    }
+    if x <= 1. && step > jumps {
+        step = jumps;
+    }
+    step / jumps
 }
 
 impl Into<EaseMethod> for EaseFunction {
@@ -599,3 +1710,285 @@ impl Default for Repeat {
         Self::Finite(1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> TrackKey {
+        (TypeId::of::<Spring>(), 0)
+    }
+
+    /// Animator playing a one second step for each of `deltas` to the translation.
+    fn translating(deltas: &[Vec3]) -> Animator {
+        let mut animator = Animator::default();
+        animator.set_exec(true);
+        for &delta in deltas {
+            animator
+                .add_change()
+                .set_duration(Duration::from_secs(1))
+                .set_translation(delta);
+        }
+        animator
+    }
+
+    /// Ticks `animator` by a quarter second `ticks` times, returns the translation it wrote.
+    fn play(animator: &mut Animator, transform: &mut Transform, ticks: usize) -> Vec3 {
+        for _ in 0..ticks {
+            animator.tick_progress(Duration::from_millis(250));
+            animator.tick_component(transform);
+        }
+        transform.translation
+    }
+
+    /// Ticks `position` at 60 fps until it rests, returns the seconds it took.
+    fn run_spring<V: Animatable>(spring: Spring, position: &mut V, target: V) -> f32 {
+        let mut velocity = StartMap::default();
+        let mut time = 0.;
+        while !spring.settle(position, target, &mut velocity, key(), 1. / 60.) {
+            time += 1. / 60.;
+            assert!(time < 10., "spring never came to rest");
+        }
+        assert!(!velocity.contains(&key()));
+        time
+    }
+
+    #[test]
+    fn spring_settles_on_target() {
+        let mut position = 0.;
+        let time = run_spring(Spring::default(), &mut position, 100.);
+        assert_eq!(position, 100.);
+        assert!(time > 0.1 && time < 2.);
+    }
+
+    #[test]
+    fn spring_keeps_velocity_between_ticks() {
+        let spring = Spring::default();
+        let mut velocity = StartMap::default();
+        let mut position = Vec2::ZERO;
+        spring.settle(&mut position, Vec2::X, &mut velocity, key(), 0.1);
+        let speed = velocity.get::<Vec2>(&key()).unwrap();
+        assert!(speed.x > 0. && speed.y == 0.);
+        let mut still = position;
+        spring.settle(&mut still, position, &mut velocity, key(), 0.1);
+        assert!(
+            still.x > position.x,
+            "velocity should carry past a new target"
+        );
+    }
+
+    #[test]
+    fn spring_rotates_along_shortest_arc() {
+        let target = Quat::from_rotation_z(170f32.to_radians());
+        let mut rotation = Quat::IDENTITY;
+        let mut velocity = StartMap::default();
+        let mut previous = rotation.angle_between(target);
+        for _ in 0..30 {
+            Spring::default().settle(&mut rotation, target, &mut velocity, key(), 1. / 60.);
+            let (axis, _) = rotation.to_axis_angle();
+            assert!(axis.z > 0.99, "left the rotation plane: {axis}");
+            let angle = rotation.angle_between(target);
+            assert!(angle <= previous + 1e-4);
+            previous = angle;
+        }
+        run_spring(Spring::default(), &mut rotation, target);
+        assert!(rotation.angle_between(target) < 1e-4);
+    }
+
+    #[test]
+    fn spring_rotation_velocity_is_angular() {
+        let spring = Spring {
+            velocity: 1.,
+            ..default()
+        };
+        let target = Quat::from_rotation_y(1.);
+        let mut rotation = Quat::IDENTITY;
+        let mut velocity = StartMap::default();
+        spring.settle(&mut rotation, target, &mut velocity, key(), 1. / 60.);
+        let speed = velocity.get::<Vec3>(&key()).unwrap();
+        assert!(speed.y > 1. && speed.x.abs() < 1e-4 && speed.z.abs() < 1e-4);
+    }
+
+    #[test]
+    fn springs_without_their_component_do_not_hold_the_step() {
+        let mut animator = Animator::default();
+        animator
+            .set_exec(true)
+            .add_change()
+            .set_translation(Vec3::X)
+            .with_spring(Spring::default())
+            .set_sprite_color(Color::WHITE)
+            .with_spring(Spring::default());
+        let mut transform = Transform::default();
+        for _ in 0..120 {
+            animator.tick_progress(Duration::from_secs_f32(1. / 60.));
+            animator.tick_component(&mut transform);
+        }
+        assert_eq!(transform.translation, Vec3::X);
+        assert!(animator.is_finished());
+    }
+
+    #[test]
+    fn cubic_bezier_matches_css_keywords() {
+        let ease = |x| cubic_bezier(0.25, 0.1, 0.25, 1., x);
+        assert_eq!(ease(0.), 0.);
+        assert_eq!(ease(1.), 1.);
+        assert!((ease(0.5) - 0.8024).abs() < 1e-3);
+        let linear = |x| cubic_bezier(0.3, 0.3, 0.7, 0.7, x);
+        for x in [0.1, 0.25, 0.5, 0.9] {
+            assert!((linear(x) - x).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn cubic_bezier_overshoots_and_solves_flat_slopes() {
+        let back = |x| cubic_bezier(0.34, 1.56, 0.64, 1., x);
+        assert!(back(0.6) > 1.);
+        let flat = |x| cubic_bezier(1., 0., 0., 1., x);
+        assert!((flat(0.5) - 0.5).abs() < 1e-3);
+        assert!(flat(0.1) < flat(0.2) && flat(0.8) < flat(0.9));
+    }
+
+    #[test]
+    fn steps_follow_css_positions() {
+        let sample = |position| [0., 0.1, 0.5, 0.99, 1.].map(|x| ease_steps(4, position, x));
+        assert_eq!(sample(StepPosition::JumpEnd), [0., 0., 0.5, 0.75, 1.]);
+        assert_eq!(sample(StepPosition::JumpStart), [0.25, 0.25, 0.75, 1., 1.]);
+        assert_eq!(sample(StepPosition::JumpBoth), [0.2, 0.2, 0.6, 0.8, 1.]);
+        assert_eq!(sample(StepPosition::JumpNone), [0., 0., 2. / 3., 1., 1.]);
+    }
+
+    #[test]
+    fn first_step_reports_its_repetitions() {
+        let mut animator = Animator::default();
+        animator
+            .set_exec(true)
+            .add_change()
+            .set_duration(Duration::from_secs(1))
+            .set_repeat(Repeat::Finite(3))
+            .set_translation(Vec3::X);
+        for _ in 0..2 {
+            animator.tick_progress(Duration::from_millis(100));
+        }
+        assert_eq!(animator.total_times(), 3.);
+        assert!(animator.progress() > 0.);
+    }
+
+    #[test]
+    fn steps_without_duration_jump_to_their_end() {
+        let mut animator = Animator::default();
+        animator
+            .set_exec(true)
+            .add_change()
+            .set_translation(Vec3::X);
+        animator
+            .add_change()
+            .set_delay(Duration::from_secs(1))
+            .set_translation(Vec3::Y);
+        let mut transform = Transform::default();
+        assert_eq!(play(&mut animator, &mut transform, 1), Vec3::X);
+        assert_eq!(play(&mut animator, &mut transform, 2), Vec3::X);
+        assert_eq!(play(&mut animator, &mut transform, 4), Vec3::X + Vec3::Y);
+        assert!(animator.is_finished());
+    }
+
+    #[test]
+    fn missing_steps_are_ignored() {
+        let mut animator = translating(&[Vec3::X, Vec3::Y]);
+        animator.transition(2, Duration::ZERO);
+        animator.start(5);
+        animator.tick_progress(Duration::from_millis(250));
+        assert_eq!(animator.elapsed(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn reversing_after_finish_does_not_start_again() {
+        let mut animator = translating(&[Vec3::X, Vec3::Y]);
+        let mut transform = Transform::default();
+        assert_eq!(play(&mut animator, &mut transform, 12), Vec3::X + Vec3::Y);
+        assert!(animator.is_finished());
+        animator.drain_events().for_each(drop);
+        animator.set_speed(-1.);
+        assert_eq!(play(&mut animator, &mut transform, 12), Vec3::ZERO);
+        let events: Vec<_> = animator.drain_events().collect();
+        assert_eq!(
+            events,
+            [
+                AnimatorEventKind::StepAdvanced { from: 1, to: 0 },
+                AnimatorEventKind::Finished
+            ]
+        );
+    }
+
+    #[test]
+    fn absolute_steps_play_backwards_and_seek_back() {
+        let mut animator = Animator::default();
+        for target in [Vec3::X * 10., Vec3::ONE * 10.] {
+            animator
+                .set_exec(true)
+                .add_change()
+                .set_duration(Duration::from_secs(1))
+                .to_translation(target);
+        }
+        let mut transform = Transform::default();
+        for (speed, end) in [
+            (1., Vec3::ONE * 10.),
+            (-1., Vec3::ZERO),
+            (1., Vec3::ONE * 10.),
+        ] {
+            animator.set_speed(speed);
+            assert_eq!(play(&mut animator, &mut transform, 12), end);
+        }
+        animator.seek(Duration::ZERO);
+        animator.tick_component(&mut transform);
+        assert_eq!(transform.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn rotation_axis_steps_add_up() {
+        let mut animator = Animator::default();
+        animator.set_exec(true);
+        for degrees in [90., -90., 90., 450.] {
+            animator
+                .add_change()
+                .set_duration(Duration::from_secs(1))
+                .set_rotation_axis(Vec3::Z, degrees);
+        }
+        let mut transform = Transform::default();
+        let mut turned = |ticks: usize, degrees: f32| {
+            play(&mut animator, &mut transform, ticks);
+            let expected = Quat::from_rotation_z(degrees.to_radians());
+            assert!(transform.rotation.angle_between(expected) < 1e-3);
+        };
+        turned(4, 90.);
+        turned(5, 0.);
+        turned(5, 90.);
+        turned(3, 90. + 225.);
+        turned(2, 90. + 450.);
+    }
+
+    #[test]
+    fn parallel_animations_replay_with_the_sequence() {
+        let mut animator = Animator::default();
+        animator
+            .set_exec(true)
+            .add_change()
+            .set_duration(Duration::from_secs(1))
+            .set_scale(Vec3::ONE);
+        animator
+            .add_parallel()
+            .set_duration(Duration::from_secs(2))
+            .set_translation(Vec3::X * 10.);
+        assert_eq!(animator.duration(), Some(Duration::from_secs(2)));
+        let mut transform = Transform::default();
+        assert_eq!(play(&mut animator, &mut transform, 12), Vec3::X * 10.);
+        assert!(animator.is_finished());
+        assert_eq!(animator.elapsed(), Duration::from_secs(2));
+        animator.start(0);
+        animator.tick_component(&mut transform);
+        assert_eq!(transform.translation, Vec3::ZERO);
+        animator.seek(Duration::from_millis(1500));
+        animator.tick_component(&mut transform);
+        assert_eq!(transform.translation, Vec3::X * 7.5);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{animator::Animator, lifecycle::Exiting};
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Plays a step of the entity's [`Animator`] whenever its [`Interaction`] changes.
+///
+/// The steps usually animate to absolute targets, e.g. `to_background`, so each one
+/// picks up from wherever the previous state left the button. Use
+/// [`Animator::set_step`] to keep a step from running into the next one.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct InteractionAnimations {
+    pub none: Option<usize>,
+    pub hovered: Option<usize>,
+    pub pressed: Option<usize>,
+    /// Cross-fade passed to [`Animator::transition`], zero starts from the current values.
+    pub blend: Duration,
+}
+
+impl Default for InteractionAnimations {
+    fn default() -> Self {
+        Self {
+            none: Some(0),
+            hovered: Some(1),
+            pressed: Some(2),
+            blend: Duration::ZERO,
+        }
+    }
+}
+
+impl InteractionAnimations {
+    /// The default steps that exist in an [`Animator`] with `count` steps.
+    pub fn for_steps(count: usize) -> Self {
+        let exists = |index: usize| (index < count).then_some(index);
+        Self {
+            none: exists(0),
+            hovered: exists(1),
+            pressed: exists(2),
+            ..default()
+        }
+    }
+
+    pub fn step(&self, interaction: Interaction) -> Option<usize> {
+        match interaction {
+            Interaction::None => self.none,
+            Interaction::Hovered => self.hovered,
+            Interaction::Pressed => self.pressed,
+        }
+    }
+}
+
+type Interacting<'a> = (&'a Interaction, &'a InteractionAnimations, &'a mut Animator);
+
+pub(crate) fn interaction_transition(
+    mut query: Query<Interacting, (Changed<Interaction>, Without<Exiting>)>,
+) {
+    for (interaction, animations, mut animator) in query.iter_mut() {
+        if let Some(index) = animations.step(*interaction) {
+            animator.resume();
+            animator.transition(index, animations.blend);
+        }
+    }
+}
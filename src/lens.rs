@@ -0,0 +1,984 @@
+use crate::{
+    ani_utils::*,
+    animator::{EaseMethod, Spring},
+    opacity::Opacity,
+};
+use bevy::{prelude::*, utils::HashMap};
+use std::{
+    any::{type_name, Any, TypeId},
+    f32::consts::PI,
+    fmt,
+    sync::Arc,
+};
+
+/// A value that can be accumulated, scaled and interpolated by an [`Animator`](crate::animator::Animator).
+pub trait Animatable: Copy + Send + Sync + fmt::Debug + 'static {
+    fn add(self, rhs: Self) -> Self;
+    fn sub(self, rhs: Self) -> Self;
+    fn scale(self, ratio: f32) -> Self;
+    /// Magnitude used to decide when a [`Spring`] has come to rest.
+    fn norm(self) -> f32;
+
+    /// Converts a step's start into the unit of `like` so both can be interpolated,
+    /// only [`Val`]s have units.
+    fn match_unit(self, _like: Self, _context: &ValContext, _axis: ValAxis) -> Self {
+        self
+    }
+
+    fn lerp(self, to: Self, ratio: f32) -> Self {
+        self.add(to.sub(self).scale(ratio))
+    }
+
+    /// Interpolates colors in `space`, everything else ignores it.
+    fn lerp_in(self, to: Self, ratio: f32, _space: ColorSpace) -> Self {
+        self.lerp(to, ratio)
+    }
+
+    /// The rotation inside `self`, a [`Spring`] moves rotations along their tangent
+    /// space instead of through `add` and `scale`.
+    fn rotation_mut(&mut self) -> Option<&mut Quat> {
+        None
+    }
+}
+
+impl Animatable for f32 {
+    fn add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+
+    fn sub(self, rhs: Self) -> Self {
+        self - rhs
+    }
+
+    fn scale(self, ratio: f32) -> Self {
+        self * ratio
+    }
+
+    fn norm(self) -> f32 {
+        self.abs()
+    }
+}
+
+macro_rules! impl_animatable_vec {
+    ($($ty:ty),*) => {
+        $(impl Animatable for $ty {
+            fn add(self, rhs: Self) -> Self {
+                self + rhs
+            }
+
+            fn sub(self, rhs: Self) -> Self {
+                self - rhs
+            }
+
+            fn scale(self, ratio: f32) -> Self {
+                self * ratio
+            }
+
+            fn norm(self) -> f32 {
+                self.length()
+            }
+        })*
+    };
+}
+
+impl_animatable_vec!(Vec2, Vec3, Vec4);
+
+/// Rotations compose by multiplication, `add` applies `rhs` after `self`.
+impl Animatable for Quat {
+    fn add(self, rhs: Self) -> Self {
+        (rhs * self).normalize()
+    }
+
+    fn sub(self, rhs: Self) -> Self {
+        (self * rhs.inverse()).normalize()
+    }
+
+    fn scale(self, ratio: f32) -> Self {
+        Quat::IDENTITY.slerp(self, ratio)
+    }
+
+    fn norm(self) -> f32 {
+        self.angle_between(Quat::IDENTITY)
+    }
+
+    fn lerp(self, to: Self, ratio: f32) -> Self {
+        self.slerp(to, ratio)
+    }
+
+    fn rotation_mut(&mut self) -> Option<&mut Quat> {
+        Some(self)
+    }
+}
+
+impl Animatable for Color {
+    fn add(self, rhs: Self) -> Self {
+        add_color(self, rhs)
+    }
+
+    fn sub(self, rhs: Self) -> Self {
+        sub_color(self, rhs)
+    }
+
+    fn scale(self, ratio: f32) -> Self {
+        mul_color(self, ratio)
+    }
+
+    fn norm(self) -> f32 {
+        Vec4::from(self).length()
+    }
+
+    fn lerp_in(self, to: Self, ratio: f32, space: ColorSpace) -> Self {
+        lerp_color(self, to, ratio, space)
+    }
+}
+
+/// What a [`Val`] property is measured against, see [`Lens::axis`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValAxis {
+    /// A horizontal offset such as `left` or a margin, `Auto` counts as zero.
+    #[default]
+    Horizontal,
+    /// A vertical offset such as `top`, `Auto` counts as zero.
+    Vertical,
+    /// A width, `Auto` counts as the node's computed width.
+    Width,
+    /// A height, `Auto` counts as the node's computed height.
+    Height,
+}
+
+/// Sizes a UI node's [`Val`]s resolve against, refreshed every frame from its `Node`,
+/// its parent's `Node` and the primary window.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ValContext {
+    /// Computed size of the node.
+    pub size: Vec2,
+    /// Size percentages refer to, the parent node or the viewport for root nodes.
+    pub parent: Vec2,
+    pub viewport: Vec2,
+}
+
+impl ValContext {
+    /// Logical pixels of `val` along `axis`.
+    pub fn to_px(&self, val: Val, axis: ValAxis) -> f32 {
+        let (size, parent) = match axis {
+            ValAxis::Horizontal | ValAxis::Width => (self.size.x, self.parent.x),
+            ValAxis::Vertical | ValAxis::Height => (self.size.y, self.parent.y),
+        };
+        match val {
+            Val::Auto if matches!(axis, ValAxis::Width | ValAxis::Height) => size,
+            Val::Auto => 0.,
+            Val::Px(v) => v,
+            Val::Percent(v) => v / 100. * parent,
+            Val::Vw(v) => v / 100. * self.viewport.x,
+            Val::Vh(v) => v / 100. * self.viewport.y,
+            Val::VMin(v) => v / 100. * self.viewport.min_element(),
+            Val::VMax(v) => v / 100. * self.viewport.max_element(),
+        }
+    }
+
+    /// `px` logical pixels in the unit of `like`, `Auto` can't be converted into.
+    pub fn from_px(&self, px: f32, like: Val, axis: ValAxis) -> Option<Val> {
+        let parent = match axis {
+            ValAxis::Horizontal | ValAxis::Width => self.parent.x,
+            ValAxis::Vertical | ValAxis::Height => self.parent.y,
+        };
+        let percent = |basis: f32| if basis == 0. { 0. } else { px / basis * 100. };
+        match like {
+            Val::Auto => None,
+            Val::Px(_) => Some(Val::Px(px)),
+            Val::Percent(_) => Some(Val::Percent(percent(parent))),
+            Val::Vw(_) => Some(Val::Vw(percent(self.viewport.x))),
+            Val::Vh(_) => Some(Val::Vh(percent(self.viewport.y))),
+            Val::VMin(_) => Some(Val::VMin(percent(self.viewport.min_element()))),
+            Val::VMax(_) => Some(Val::VMax(percent(self.viewport.max_element()))),
+        }
+    }
+}
+
+impl Animatable for Val {
+    fn add(self, rhs: Self) -> Self {
+        add_val(self, rhs)
+    }
+
+    fn sub(self, rhs: Self) -> Self {
+        sub_val(self, rhs)
+    }
+
+    fn scale(self, ratio: f32) -> Self {
+        mul_val(self, ratio)
+    }
+
+    fn norm(self) -> f32 {
+        match self {
+            Val::Auto => 0.,
+            Val::Px(v)
+            | Val::Percent(v)
+            | Val::Vw(v)
+            | Val::Vh(v)
+            | Val::VMin(v)
+            | Val::VMax(v) => v.abs(),
+        }
+    }
+
+    fn match_unit(self, like: Self, context: &ValContext, axis: ValAxis) -> Self {
+        if std::mem::discriminant(&self) == std::mem::discriminant(&like) {
+            return self;
+        }
+        context
+            .from_px(context.to_px(self, axis), like, axis)
+            .unwrap_or(self)
+    }
+}
+
+/// Each side is animated with the arithmetic of [`Val`].
+impl Animatable for UiRect {
+    fn add(self, rhs: Self) -> Self {
+        UiRect {
+            left: self.left.add(rhs.left),
+            right: self.right.add(rhs.right),
+            top: self.top.add(rhs.top),
+            bottom: self.bottom.add(rhs.bottom),
+        }
+    }
+
+    fn sub(self, rhs: Self) -> Self {
+        UiRect {
+            left: self.left.sub(rhs.left),
+            right: self.right.sub(rhs.right),
+            top: self.top.sub(rhs.top),
+            bottom: self.bottom.sub(rhs.bottom),
+        }
+    }
+
+    fn scale(self, ratio: f32) -> Self {
+        UiRect {
+            left: self.left.scale(ratio),
+            right: self.right.scale(ratio),
+            top: self.top.scale(ratio),
+            bottom: self.bottom.scale(ratio),
+        }
+    }
+
+    fn norm(self) -> f32 {
+        [self.left, self.right, self.top, self.bottom]
+            .map(Val::norm)
+            .into_iter()
+            .fold(0., f32::max)
+    }
+
+    /// Percentages of every side refer to the parent's width, like CSS margins.
+    fn match_unit(self, like: Self, context: &ValContext, _axis: ValAxis) -> Self {
+        UiRect {
+            left: self
+                .left
+                .match_unit(like.left, context, ValAxis::Horizontal),
+            right: self
+                .right
+                .match_unit(like.right, context, ValAxis::Horizontal),
+            top: self.top.match_unit(like.top, context, ValAxis::Horizontal),
+            bottom: self
+                .bottom
+                .match_unit(like.bottom, context, ValAxis::Horizontal),
+        }
+    }
+}
+
+/// Values a property passes through at normalized times of a repetition.
+///
+/// The start of the step is an implicit keyframe at `0.`, each keyframe's
+/// [`EaseMethod`] eases the segment leading into it. Times are read against the
+/// step's eased ratio, so keep the step [`EaseMethod::Linear`] for CSS like timing.
+///
+/// ```ignore
+/// Keyframes::new()
+///     .key(0.3, Vec3::new(0., 40., 0.), EaseFunction::QuadraticOut.into())
+///     .key(1., Vec3::ZERO, EaseFunction::BounceOut.into())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Keyframes<V> {
+    frames: Vec<(f32, V, EaseMethod)>,
+}
+
+impl<V> Default for Keyframes<V> {
+    fn default() -> Self {
+        Self { frames: Vec::new() }
+    }
+}
+
+impl<V: Animatable> Keyframes<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reaches `value` at `time` in `0.0..=1.0`, easing from the previous keyframe.
+    pub fn key(mut self, time: f32, value: V, ease_method: EaseMethod) -> Self {
+        let time = time.clamp(0., 1.);
+        let index = self.frames.partition_point(|(other, ..)| *other <= time);
+        self.frames.insert(index, (time, value, ease_method));
+        self
+    }
+
+    /// Value at `ratio` of a path starting at `origin`, `end` turns keyframe values into
+    /// the values to pass through.
+    fn sample(&self, origin: V, end: impl Fn(V) -> V, ratio: f32, space: ColorSpace) -> V {
+        let mut prev = (0., origin);
+        for (time, value, ease_method) in &self.frames {
+            let value = end(*value);
+            if ratio <= *time {
+                let span = time - prev.0;
+                let local = if span > 0. {
+                    (ratio - prev.0) / span
+                } else {
+                    1.
+                };
+                return prev
+                    .1
+                    .lerp_in(value, ease_method.tick(local.max(0.)), space);
+            }
+            prev = (*time, value);
+        }
+        prev.1
+    }
+
+    fn first(&self) -> Option<V> {
+        self.frames.first().map(|(_, value, _)| *value)
+    }
+}
+
+/// Reads and writes one [`Animatable`] property of a component.
+///
+/// The component's system is added by
+/// [`AnimationApp::register_lens`](crate::plugin::AnimationApp::register_lens).
+pub trait Lens: Send + Sync + 'static {
+    type Target: Component;
+    type Value: Animatable;
+
+    fn get(&self, target: &Self::Target) -> Self::Value;
+    fn set(&self, target: &mut Self::Target, value: Self::Value);
+
+    /// Tells apart lenses of the same type that point at different properties.
+    fn id(&self) -> u64 {
+        0
+    }
+
+    /// What a [`Val`] property converts its units against.
+    fn axis(&self) -> ValAxis {
+        ValAxis::Horizontal
+    }
+}
+
+macro_rules! lens {
+    ($(#[$meta:meta])* $name:ident, $target:ty, $value:ty, $($field:tt).+; $axis:expr) => {
+        lens!($(#[$meta])* $name, $target, $value, $($field).+ {
+            fn axis(&self) -> ValAxis {
+                $axis
+            }
+        });
+    };
+    ($(#[$meta:meta])* $name:ident, $target:ty, $value:ty, $($field:tt).+) => {
+        lens!($(#[$meta])* $name, $target, $value, $($field).+ {});
+    };
+    ($(#[$meta:meta])* $name:ident, $target:ty, $value:ty, $($field:tt).+ { $($axis:item)? }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default)]
+        pub struct $name;
+
+        impl Lens for $name {
+            type Target = $target;
+            type Value = $value;
+
+            fn get(&self, target: &$target) -> $value {
+                target.$($field).+
+            }
+
+            fn set(&self, target: &mut $target, value: $value) {
+                target.$($field).+ = value;
+            }
+
+            $($axis)?
+        }
+    };
+}
+
+lens!(StyleWidthLens, Style, Val, width; ValAxis::Width);
+lens!(StyleHeightLens, Style, Val, height; ValAxis::Height);
+lens!(StyleLeftLens, Style, Val, left);
+lens!(StyleRightLens, Style, Val, right);
+lens!(StyleTopLens, Style, Val, top; ValAxis::Vertical);
+lens!(StyleBottomLens, Style, Val, bottom; ValAxis::Vertical);
+lens!(StyleMinWidthLens, Style, Val, min_width; ValAxis::Width);
+lens!(StyleMinHeightLens, Style, Val, min_height; ValAxis::Height);
+lens!(StyleMaxWidthLens, Style, Val, max_width; ValAxis::Width);
+lens!(StyleMaxHeightLens, Style, Val, max_height; ValAxis::Height);
+lens!(StyleMarginLens, Style, UiRect, margin);
+lens!(StylePaddingLens, Style, UiRect, padding);
+lens!(StyleBorderLens, Style, UiRect, border);
+lens!(StyleColumnGapLens, Style, Val, column_gap);
+lens!(StyleRowGapLens, Style, Val, row_gap; ValAxis::Vertical);
+lens!(StyleFlexBasisLens, Style, Val, flex_basis; ValAxis::Width);
+lens!(StyleFlexGrowLens, Style, f32, flex_grow);
+lens!(BackgroundColorLens, BackgroundColor, Color, 0);
+lens!(BorderColorLens, BorderColor, Color, 0);
+lens!(OutlineColorLens, Outline, Color, color);
+lens!(OutlineWidthLens, Outline, Val, width);
+lens!(OutlineOffsetLens, Outline, Val, offset);
+
+/// Tint of a `UiImage`, which is the node's `BackgroundColor` in this version of bevy.
+pub use self::BackgroundColorLens as ImageTintLens;
+lens!(TransformTranslationLens, Transform, Vec3, translation);
+lens!(TransformRotationLens, Transform, Quat, rotation);
+lens!(TransformScaleLens, Transform, Vec3, scale);
+lens!(SpriteColorLens, Sprite, Color, color);
+lens!(OpacityLens, Opacity, f32, 0);
+
+/// Font size of the text section at the given index.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TextFontSizeLens(pub usize);
+
+impl Lens for TextFontSizeLens {
+    type Target = Text;
+    type Value = f32;
+
+    fn get(&self, target: &Text) -> f32 {
+        target
+            .sections
+            .get(self.0)
+            .map_or(0., |section| section.style.font_size)
+    }
+
+    fn set(&self, target: &mut Text, value: f32) {
+        if let Some(section) = target.sections.get_mut(self.0) {
+            section.style.font_size = value;
+        }
+    }
+
+    fn id(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
+/// Color of the text section at the given index.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TextColorLens(pub usize);
+
+impl Lens for TextColorLens {
+    type Target = Text;
+    type Value = Color;
+
+    fn get(&self, target: &Text) -> Color {
+        target
+            .sections
+            .get(self.0)
+            .map_or(Color::NONE, |section| section.style.color)
+    }
+
+    fn set(&self, target: &mut Text, value: Color) {
+        if let Some(section) = target.sections.get_mut(self.0) {
+            section.style.color = value;
+        }
+    }
+
+    fn id(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
+/// `Transform::rotation` as Euler angles in degrees, which unlike a quaternion can
+/// describe several full turns.
+#[derive(Clone, Copy, Debug)]
+pub struct TransformEulerLens(pub EulerRot);
+
+impl Default for TransformEulerLens {
+    fn default() -> Self {
+        TransformEulerLens(EulerRot::XYZ)
+    }
+}
+
+impl Lens for TransformEulerLens {
+    type Target = Transform;
+    type Value = Vec3;
+
+    fn get(&self, target: &Transform) -> Vec3 {
+        let (a, b, c) = target.rotation.to_euler(self.0);
+        Vec3::new(a, b, c) * (180. / PI)
+    }
+
+    fn set(&self, target: &mut Transform, value: Vec3) {
+        let value = value * (PI / 180.);
+        target.rotation = Quat::from_euler(self.0, value.x, value.y, value.z);
+    }
+
+    fn id(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
+/// Angle in degrees `Transform::rotation` is turned around the local `axis`, the rest of
+/// the rotation is kept, so spins can run past 360°.
+#[derive(Clone, Copy, Debug)]
+pub struct TransformAxisAngleLens {
+    pub axis: Vec3,
+}
+
+impl TransformAxisAngleLens {
+    /// Splits `rotation` into `swing * twist` with `twist` around the axis.
+    fn twist(&self, rotation: Quat) -> (Quat, f32) {
+        let axis = self.axis.normalize_or_zero();
+        let projected = axis * rotation.xyz().dot(axis);
+        let twist = Quat::from_xyzw(projected.x, projected.y, projected.z, rotation.w);
+        if twist.length_squared() <= f32::EPSILON {
+            return (rotation, 0.);
+        }
+        let twist = twist.normalize();
+        let angle = 2. * twist.xyz().dot(axis).atan2(twist.w);
+        (rotation * twist.inverse(), angle.to_degrees())
+    }
+}
+
+impl Lens for TransformAxisAngleLens {
+    type Target = Transform;
+    type Value = f32;
+
+    fn get(&self, target: &Transform) -> f32 {
+        self.twist(target.rotation).1
+    }
+
+    fn set(&self, target: &mut Transform, value: f32) {
+        let (swing, _) = self.twist(target.rotation);
+        let twist = Quat::from_axis_angle(self.axis.normalize_or_zero(), value.to_radians());
+        target.rotation = (swing * twist).normalize();
+    }
+
+    fn id(&self) -> u64 {
+        let [x, y, z] = self.axis.to_array().map(|v| v.to_bits() as u64);
+        x ^ y.rotate_left(21) ^ z.rotate_left(42)
+    }
+}
+
+/// `Sprite::custom_size`, a sprite without one starts from zero.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpriteSizeLens;
+
+impl Lens for SpriteSizeLens {
+    type Target = Sprite;
+    type Value = Vec2;
+
+    fn get(&self, target: &Sprite) -> Vec2 {
+        target.custom_size.unwrap_or_default()
+    }
+
+    fn set(&self, target: &mut Sprite, value: Vec2) {
+        target.custom_size = Some(value);
+    }
+}
+
+pub(crate) type TrackKey = (TypeId, u64);
+
+/// Per property value every step of an [`Animator`](crate::animator::Animator) starts from.
+#[derive(Default)]
+pub(crate) struct StartMap(HashMap<TrackKey, Box<dyn Any + Send + Sync>>);
+
+impl StartMap {
+    pub fn contains(&self, key: &TrackKey) -> bool {
+        self.0.contains_key(key)
+    }
+
+    pub fn get<V: Animatable>(&self, key: &TrackKey) -> Option<V> {
+        self.0
+            .get(key)
+            .and_then(|value| value.downcast_ref::<V>())
+            .copied()
+    }
+
+    pub fn insert<V: Animatable>(&mut self, key: TrackKey, value: V) {
+        self.0.insert(key, Box::new(value));
+    }
+
+    pub fn remove(&mut self, key: &TrackKey) {
+        self.0.remove(key);
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(&TrackKey) -> bool) {
+        self.0.retain(|key, _| keep(key));
+    }
+}
+
+impl fmt::Debug for StartMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StartMap")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TrackMode {
+    /// The value is a delta added to the start of the step.
+    Relative,
+    /// The value is a target, the step starts from the component's current value.
+    Absolute,
+}
+
+/// What a track moves its property through during a step.
+#[derive(Clone, Debug)]
+pub(crate) enum Curve<V> {
+    /// A delta or a target depending on the [`TrackMode`].
+    Value(V),
+    /// Deltas or targets at several times of the step.
+    Keyframes(Keyframes<V>),
+}
+
+impl<V: Animatable> Curve<V> {
+    /// First value the curve moves to, its unit is the one the step animates in.
+    pub fn first(&self) -> Option<V> {
+        match self {
+            Curve::Value(value) => Some(*value),
+            Curve::Keyframes(frames) => frames.first(),
+        }
+    }
+
+    /// Property value at `ratio` of a step that started at `from`, interpolated in `space`.
+    pub fn sample(&self, mode: TrackMode, from: V, ratio: f32, space: ColorSpace) -> V {
+        let end = |value: V| match mode {
+            TrackMode::Relative => from.add(value),
+            TrackMode::Absolute => value,
+        };
+        match self {
+            Curve::Value(value) => from.lerp_in(end(*value), ratio, space),
+            Curve::Keyframes(frames) => frames.sample(from, end, ratio, space),
+        }
+    }
+
+    /// How far the property moved away from `from` at `ratio`.
+    pub fn offset(&self, mode: TrackMode, from: V, ratio: f32, space: ColorSpace) -> V {
+        self.sample(mode, from, ratio, space).sub(from)
+    }
+
+    /// Start of the next step once this one played up to `ratio`, `undo` moves a
+    /// relative start back before the step instead.
+    pub fn shifted(
+        &self,
+        mode: TrackMode,
+        from: V,
+        ratio: f32,
+        space: ColorSpace,
+        undo: bool,
+    ) -> V {
+        match mode {
+            TrackMode::Relative if undo => {
+                from.add(self.offset(mode, from, ratio, space).scale(-1.))
+            }
+            TrackMode::Absolute if undo || ratio <= 0. => from,
+            _ => self.sample(mode, from, ratio, space),
+        }
+    }
+}
+
+/// Type erased property of an [`Animation`](crate::animator::Animation).
+pub(crate) trait Track: Send + Sync + fmt::Debug {
+    fn component(&self) -> TypeId;
+    fn key(&self) -> TrackKey;
+    fn mode(&self) -> TrackMode;
+    /// Overrides the [`EaseMethod`] of the step the track belongs to.
+    fn ease(&self) -> Option<&EaseMethod>;
+    fn set_ease(&mut self, ease: EaseMethod);
+    /// Replaces the eased timing with a [`Spring`] toward the step's end value.
+    fn spring(&self) -> Option<&Spring>;
+    fn set_spring(&mut self, spring: Spring);
+    fn set_color_space(&mut self, space: ColorSpace);
+    /// `"Component::field.path"` of tracks written through reflection.
+    fn reflect_path(&self) -> Option<&Arc<str>> {
+        None
+    }
+    /// Stores the component's current value as the start of the step.
+    fn capture(&self, component: &dyn Any, start: &mut StartMap);
+    fn apply(&self, component: &mut dyn Any, start: &StartMap, ratio: f32);
+    /// Adds what this track changes at `ratio` on top of the component's current value.
+    fn add_offset(&self, component: &mut dyn Any, start: &StartMap, ratio: f32);
+    /// Moves the start past this step as if it had played up to `ratio`,
+    /// or back before a relative step when undoing.
+    fn shift(&self, start: &mut StartMap, ratio: f32, undo: bool);
+    /// Copies the start of this track from `from` into `to`.
+    fn copy_start(&self, from: &StartMap, to: &mut StartMap);
+    /// Converts the start into the units the step animates in.
+    fn resolve(&self, start: &mut StartMap, context: &ValContext);
+    /// Cross-fades from the values in `from` to what [`Track::apply`] wrote, `weight`
+    /// goes from `0` at the interruption to `1` once the blend is over.
+    fn blend(&self, component: &mut dyn Any, from: &StartMap, weight: f32);
+    /// Springs the component toward the step's end value for `dt` seconds,
+    /// returns whether it came to rest.
+    fn settle(
+        &self,
+        component: &mut dyn Any,
+        start: &StartMap,
+        velocity: &mut StartMap,
+        dt: f32,
+    ) -> bool;
+}
+
+pub(crate) struct PropertyTrack<L: Lens> {
+    pub lens: L,
+    pub curve: Curve<L::Value>,
+    pub mode: TrackMode,
+    pub ease: Option<EaseMethod>,
+    pub spring: Option<Spring>,
+    pub space: ColorSpace,
+}
+
+impl<L: Lens> fmt::Debug for PropertyTrack<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PropertyTrack")
+            .field("lens", &type_name::<L>())
+            .field("curve", &self.curve)
+            .field("mode", &self.mode)
+            .field("ease", &self.ease)
+            .field("spring", &self.spring)
+            .field("space", &self.space)
+            .finish()
+    }
+}
+
+impl<L: Lens> Track for PropertyTrack<L> {
+    fn component(&self) -> TypeId {
+        TypeId::of::<L::Target>()
+    }
+
+    fn key(&self) -> TrackKey {
+        (TypeId::of::<L>(), self.lens.id())
+    }
+
+    fn mode(&self) -> TrackMode {
+        self.mode
+    }
+
+    fn ease(&self) -> Option<&EaseMethod> {
+        self.ease.as_ref()
+    }
+
+    fn set_ease(&mut self, ease: EaseMethod) {
+        self.ease = Some(ease);
+    }
+
+    fn spring(&self) -> Option<&Spring> {
+        self.spring.as_ref()
+    }
+
+    fn set_spring(&mut self, spring: Spring) {
+        self.spring = Some(spring);
+    }
+
+    fn set_color_space(&mut self, space: ColorSpace) {
+        self.space = space;
+    }
+
+    fn capture(&self, component: &dyn Any, start: &mut StartMap) {
+        if let Some(component) = component.downcast_ref::<L::Target>() {
+            start.insert(self.key(), self.lens.get(component));
+        }
+    }
+
+    fn apply(&self, component: &mut dyn Any, start: &StartMap, ratio: f32) {
+        let (Some(component), Some(from)) = (
+            component.downcast_mut::<L::Target>(),
+            start.get::<L::Value>(&self.key()),
+        ) else {
+            return;
+        };
+        self.lens.set(
+            component,
+            self.curve.sample(self.mode, from, ratio, self.space),
+        );
+    }
+
+    fn add_offset(&self, component: &mut dyn Any, start: &StartMap, ratio: f32) {
+        let (Some(component), Some(from)) = (
+            component.downcast_mut::<L::Target>(),
+            start.get::<L::Value>(&self.key()),
+        ) else {
+            return;
+        };
+        let offset = self.curve.offset(self.mode, from, ratio, self.space);
+        let value = self.lens.get(component).add(offset);
+        self.lens.set(component, value);
+    }
+
+    fn shift(&self, start: &mut StartMap, ratio: f32, undo: bool) {
+        let key = self.key();
+        if let Some(from) = start.get::<L::Value>(&key) {
+            let start_value = self.curve.shifted(self.mode, from, ratio, self.space, undo);
+            start.insert(key, start_value);
+        }
+    }
+
+    fn copy_start(&self, from: &StartMap, to: &mut StartMap) {
+        let key = self.key();
+        if let Some(value) = from.get::<L::Value>(&key) {
+            to.insert(key, value);
+        }
+    }
+
+    fn resolve(&self, start: &mut StartMap, context: &ValContext) {
+        let key = self.key();
+        if let (Some(from), Some(like)) = (start.get::<L::Value>(&key), self.curve.first()) {
+            start.insert(key, from.match_unit(like, context, self.lens.axis()));
+        }
+    }
+
+    fn blend(&self, component: &mut dyn Any, from: &StartMap, weight: f32) {
+        let (Some(component), Some(from)) = (
+            component.downcast_mut::<L::Target>(),
+            from.get::<L::Value>(&self.key()),
+        ) else {
+            return;
+        };
+        let value = from.lerp_in(self.lens.get(component), weight, self.space);
+        self.lens.set(component, value);
+    }
+
+    fn settle(
+        &self,
+        component: &mut dyn Any,
+        start: &StartMap,
+        velocity: &mut StartMap,
+        dt: f32,
+    ) -> bool {
+        let key = self.key();
+        let (Some(component), Some(from), Some(spring)) = (
+            component.downcast_mut::<L::Target>(),
+            start.get::<L::Value>(&key),
+            &self.spring,
+        ) else {
+            return true;
+        };
+        let target = self.curve.sample(self.mode, from, 1., self.space);
+        let mut position = self.lens.get(component);
+        let rest = spring.settle(&mut position, target, velocity, key, dt);
+        self.lens.set(component, position);
+        rest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_same(a: Quat, b: Quat) {
+        assert!(a.angle_between(b) < 1e-3, "{a} != {b}");
+    }
+
+    #[test]
+    fn axis_angle_round_trips_past_a_full_turn() {
+        let lens = TransformAxisAngleLens { axis: Vec3::Z };
+        for degrees in [30., 400., 725., -500.] {
+            let mut transform = Transform::default();
+            lens.set(&mut transform, degrees);
+            assert_same(
+                transform.rotation,
+                Quat::from_rotation_z(f32::to_radians(degrees)),
+            );
+            let angle = lens.get(&transform);
+            let turns = (angle - degrees) / 360.;
+            assert!(
+                (turns - turns.round()).abs() < 1e-4,
+                "{angle} for {degrees}"
+            );
+            let mut again = Transform::default();
+            lens.set(&mut again, angle);
+            assert_same(again.rotation, transform.rotation);
+        }
+    }
+
+    #[test]
+    fn axis_angle_keeps_the_swing() {
+        let swing = Quat::from_rotation_x(0.5);
+        let lens = TransformAxisAngleLens { axis: Vec3::Z * 2. };
+        let mut transform = Transform::from_rotation(swing * Quat::from_rotation_z(0.7));
+        assert!((lens.get(&transform) - 0.7f32.to_degrees()).abs() < 1e-3);
+        lens.set(&mut transform, 100.);
+        assert_same(
+            transform.rotation,
+            swing * Quat::from_rotation_z(100f32.to_radians()),
+        );
+        assert!((lens.get(&transform) - 100.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn euler_round_trips() {
+        let lens = TransformEulerLens(EulerRot::YXZ);
+        let mut transform = Transform::default();
+        lens.set(&mut transform, Vec3::new(10., 20., 30.));
+        assert_same(
+            transform.rotation,
+            Quat::from_euler(
+                EulerRot::YXZ,
+                10f32.to_radians(),
+                20f32.to_radians(),
+                30f32.to_radians(),
+            ),
+        );
+        assert!(lens
+            .get(&transform)
+            .abs_diff_eq(Vec3::new(10., 20., 30.), 1e-3));
+    }
+
+    fn context() -> ValContext {
+        ValContext {
+            size: Vec2::new(50., 20.),
+            parent: Vec2::new(200., 100.),
+            viewport: Vec2::new(800., 400.),
+        }
+    }
+
+    #[test]
+    fn percent_converts_against_the_parent() {
+        let context = context();
+        let px = Val::Percent(25.).match_unit(Val::Px(0.), &context, ValAxis::Width);
+        assert_eq!(px, Val::Px(50.));
+        let px = Val::Percent(25.).match_unit(Val::Px(0.), &context, ValAxis::Vertical);
+        assert_eq!(px, Val::Px(25.));
+        let percent = Val::Px(50.).match_unit(Val::Percent(0.), &context, ValAxis::Height);
+        assert_eq!(percent, Val::Percent(50.));
+    }
+
+    #[test]
+    fn auto_is_the_node_size_for_sizes_only() {
+        let context = context();
+        let width = Val::Auto.match_unit(Val::Px(0.), &context, ValAxis::Width);
+        assert_eq!(width, Val::Px(50.));
+        let left = Val::Auto.match_unit(Val::Px(0.), &context, ValAxis::Horizontal);
+        assert_eq!(left, Val::Px(0.));
+        let auto = Val::Px(10.).match_unit(Val::Auto, &context, ValAxis::Width);
+        assert_eq!(auto, Val::Px(10.));
+    }
+
+    #[test]
+    fn viewport_units_convert_against_the_window() {
+        let context = context();
+        let vw = Val::Px(200.).match_unit(Val::Vw(0.), &context, ValAxis::Height);
+        assert_eq!(vw, Val::Vw(25.));
+        let vmin = Val::Vw(25.).match_unit(Val::VMin(0.), &context, ValAxis::Width);
+        assert_eq!(vmin, Val::VMin(50.));
+    }
+
+    #[test]
+    fn zero_parent_converts_to_zero_percent() {
+        let context = ValContext {
+            parent: Vec2::ZERO,
+            ..context()
+        };
+        let percent = Val::Px(30.).match_unit(Val::Percent(0.), &context, ValAxis::Width);
+        assert_eq!(percent, Val::Percent(0.));
+        let px = Val::Percent(50.).match_unit(Val::Px(0.), &context, ValAxis::Width);
+        assert_eq!(px, Val::Px(0.));
+    }
+
+    #[test]
+    fn rect_percentages_refer_to_the_parent_width() {
+        let margin = UiRect::all(Val::Percent(10.)).match_unit(
+            UiRect::all(Val::Px(0.)),
+            &context(),
+            ValAxis::Height,
+        );
+        assert_eq!(margin, UiRect::all(Val::Px(20.)));
+    }
+}
@@ -1,6 +1,14 @@
 pub mod ani_utils;
 pub mod animator;
+pub mod interaction;
+pub mod lens;
+pub mod lifecycle;
+pub mod opacity;
 pub mod plugin;
+pub mod reflect;
+pub mod stagger;
+pub mod state;
+pub mod timeline;
 pub mod ui;
 
 
@@ -0,0 +1,101 @@
+use crate::animator::Animator;
+use bevy::{
+    ecs::{system::EntityCommands, world::EntityWorldMut},
+    prelude::*,
+};
+
+/// Steps of the entity's [`Animator`] played when it is spawned and before it is despawned
+/// with [`DespawnAnimatedExt::despawn_animated`].
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct EnterExit {
+    pub enter: Option<usize>,
+    pub exit: Option<usize>,
+}
+
+/// Root waiting for the exit animations in its hierarchy before it is despawned.
+#[derive(Component)]
+pub(crate) struct Despawning;
+
+/// Entity playing its [`EnterExit::exit`] step.
+#[derive(Component)]
+pub(crate) struct Exiting;
+
+pub trait DespawnAnimatedExt {
+    /// Plays the exit step of the entity and every descendant with an [`EnterExit`],
+    /// then despawns the entity recursively once all of them completed.
+    fn despawn_animated(&mut self);
+}
+
+impl DespawnAnimatedExt for EntityCommands<'_, '_, '_> {
+    fn despawn_animated(&mut self) {
+        self.add(|root: EntityWorldMut| {
+            let root_id = root.id();
+            let world = root.into_world_mut();
+            let mut pending = vec![root_id];
+            let mut exiting = false;
+            while let Some(entity) = pending.pop() {
+                let Some(mut entity) = world.get_entity_mut(entity) else {
+                    continue;
+                };
+                if let Some(children) = entity.get::<Children>() {
+                    pending.extend(children);
+                }
+                let exit = entity
+                    .get::<EnterExit>()
+                    .and_then(|enter_exit| enter_exit.exit);
+                let Some(exit) = exit else {
+                    continue;
+                };
+                let Some(mut animator) = entity.get_mut::<Animator>() else {
+                    continue;
+                };
+                animator.set_loop(false).set_step(true).resume();
+                animator.transition(exit, default());
+                entity.insert(Exiting);
+                exiting = true;
+            }
+            if exiting {
+                world.entity_mut(root_id).insert(Despawning);
+            } else {
+                world.entity_mut(root_id).despawn_recursive();
+            }
+        });
+    }
+}
+
+pub(crate) fn play_enter(mut query: Query<(&EnterExit, &mut Animator), Added<EnterExit>>) {
+    for (enter_exit, mut animator) in query.iter_mut() {
+        if let Some(enter) = enter_exit.enter {
+            animator.resume();
+            animator.start(enter);
+        }
+    }
+}
+
+pub(crate) fn despawn_exited(
+    mut commands: Commands,
+    roots: Query<Entity, With<Despawning>>,
+    children: Query<&Children>,
+    mut exiting: Query<(&EnterExit, &mut Animator), With<Exiting>>,
+) {
+    for root in roots.iter() {
+        let mut complete = true;
+        for entity in std::iter::once(root).chain(children.iter_descendants(root)) {
+            let Ok((enter_exit, mut animator)) = exiting.get_mut(entity) else {
+                continue;
+            };
+            let Some(exit) = enter_exit.exit else {
+                continue;
+            };
+            if animator.current_step() != exit {
+                animator.transition(exit, default());
+                complete = false;
+            } else if !animator.is_step_complete() {
+                complete = false;
+            }
+        }
+        if complete {
+            commands.entity(root).despawn_recursive();
+        }
+    }
+}
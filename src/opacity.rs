@@ -0,0 +1,175 @@
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+/// Alpha multiplier for the entity and all its descendants, nested opacities multiply.
+///
+/// Applies to `BackgroundColor`, which is also the tint of `UiImage`s, `BorderColor`,
+/// text sections and `Sprite`s.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Opacity(pub f32);
+
+impl Default for Opacity {
+    fn default() -> Self {
+        Opacity(1.)
+    }
+}
+
+/// Alpha the entity's colors had before fading and the alpha written in their place,
+/// keyed by color slot.
+#[derive(Component, Default)]
+pub(crate) struct Faded(HashMap<usize, (f32, f32)>);
+
+impl Faded {
+    /// Multiplies `opacity` into the alpha of the color in `target`, whose alpha is the
+    /// base again since [`restore_opacity`] ran.
+    fn fade<T>(
+        &mut self,
+        slot: usize,
+        mut target: Mut<T>,
+        color: impl FnOnce(&mut T) -> &mut Color,
+        opacity: f32,
+    ) {
+        let color = color(target.bypass_change_detection());
+        let base = color.a();
+        let written = base * opacity;
+        color.set_a(written);
+        let before = self
+            .0
+            .insert(slot, (base, written))
+            .map_or(base, |(_, written)| written);
+        if before != written {
+            target.set_changed();
+        }
+    }
+
+    /// Puts the base alpha back unless something else wrote a new one since fading.
+    fn restore<T>(
+        &self,
+        slot: usize,
+        mut target: Mut<T>,
+        color: impl FnOnce(&mut T) -> &mut Color,
+    ) {
+        if let Some(&(base, written)) = self.0.get(&slot) {
+            let color = color(target.bypass_change_detection());
+            if color.a() == written {
+                color.set_a(base);
+            }
+        }
+    }
+}
+
+type FadeTargets<'a> = (
+    Option<&'a mut Faded>,
+    Option<&'a mut BackgroundColor>,
+    Option<&'a mut BorderColor>,
+    Option<&'a mut Text>,
+    Option<&'a mut Sprite>,
+);
+
+type Colored = Or<(
+    With<BackgroundColor>,
+    With<BorderColor>,
+    With<Text>,
+    With<Sprite>,
+)>;
+
+/// Gives faded colors their own alpha back so animators read and write unfaded values.
+pub(crate) fn restore_opacity(mut targets: Query<FadeTargets, With<Faded>>) {
+    for (faded, background, border, text, sprite) in targets.iter_mut() {
+        let Some(faded) = faded else {
+            continue;
+        };
+        if let Some(background) = background {
+            faded.restore(0, background, |background| &mut background.0);
+        }
+        if let Some(border) = border {
+            faded.restore(1, border, |border| &mut border.0);
+        }
+        if let Some(sprite) = sprite {
+            faded.restore(2, sprite, |sprite| &mut sprite.color);
+        }
+        if let Some(mut text) = text {
+            for index in 0..text.sections.len() {
+                faded.restore(3 + index, text.reborrow(), |text| {
+                    &mut text.sections[index].style.color
+                });
+            }
+        }
+    }
+}
+
+/// Fades the colors of every descendant of an [`Opacity`], and restores the ones that
+/// are no longer below any.
+pub(crate) fn propagate_opacity(
+    mut commands: Commands,
+    opacities: Query<(Entity, &Opacity)>,
+    parents: Query<&Parent>,
+    children: Query<&Children>,
+    mut targets: Query<FadeTargets, Colored>,
+    faded: Query<Entity, With<Faded>>,
+) {
+    let mut visited = HashSet::new();
+    for (root, _) in opacities.iter() {
+        let nested = parents
+            .iter_ancestors(root)
+            .any(|ancestor| opacities.contains(ancestor));
+        if nested {
+            continue;
+        }
+        let mut stack = vec![(root, 1.)];
+        while let Some((entity, inherited)) = stack.pop() {
+            let opacity = opacities
+                .get(entity)
+                .map_or(inherited, |(_, own)| inherited * own.0);
+            visited.insert(entity);
+            fade(&mut commands, &mut targets, entity, opacity);
+            if let Ok(children) = children.get(entity) {
+                stack.extend(children.iter().map(|&child| (child, opacity)));
+            }
+        }
+    }
+    for entity in faded.iter().filter(|entity| !visited.contains(entity)) {
+        fade(&mut commands, &mut targets, entity, 1.);
+        commands.entity(entity).remove::<Faded>();
+    }
+}
+
+fn fade(
+    commands: &mut Commands,
+    targets: &mut Query<FadeTargets, Colored>,
+    entity: Entity,
+    opacity: f32,
+) {
+    let Ok((faded, background, border, text, sprite)) = targets.get_mut(entity) else {
+        return;
+    };
+    let mut inserted = Faded::default();
+    let faded = match faded {
+        Some(faded) => faded.into_inner(),
+        None => &mut inserted,
+    };
+    if let Some(background) = background {
+        faded.fade(0, background, |background| &mut background.0, opacity);
+    }
+    if let Some(border) = border {
+        faded.fade(1, border, |border| &mut border.0, opacity);
+    }
+    if let Some(sprite) = sprite {
+        faded.fade(2, sprite, |sprite| &mut sprite.color, opacity);
+    }
+    if let Some(mut text) = text {
+        for index in 0..text.sections.len() {
+            faded.fade(
+                3 + index,
+                text.reborrow(),
+                |text| &mut text.sections[index].style.color,
+                opacity,
+            );
+        }
+    }
+    if !inserted.0.is_empty() {
+        commands.entity(entity).insert(inserted);
+    }
+}
@@ -1,51 +1,154 @@
-use bevy::prelude::*;
+use std::any::TypeId;
 
-use crate::animator::Animator;
+use bevy::{prelude::*, ui::UiSystem, utils::HashSet, window::PrimaryWindow};
+
+use crate::{
+    animator::{Animator, AnimatorEvent},
+    interaction::interaction_transition,
+    lens::*,
+    lifecycle::{despawn_exited, play_enter},
+    opacity::{propagate_opacity, restore_opacity},
+    reflect::animate_reflect,
+    stagger::apply_stagger,
+    state::{delay_state_change, state_transition, AnimatedNextState},
+    timeline::drive_timelines,
+};
 pub struct AnimationPlugin;
 
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnimationSet {
+    /// Advances every [`Animator`].
+    Progress,
+    /// Writes the animated properties into their components.
+    Apply,
+}
+
 impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, update_progress);
-        app.add_systems(Update, update_text);
-        app.add_systems(Update, update_style);
-        app.add_systems(Update, update_transform);
-        app.add_systems(Update, update_background);
-        app.add_systems(Update, update_sprite);
+        app.add_event::<AnimatorEvent>();
+        app.configure_sets(
+            Update,
+            (AnimationSet::Progress, AnimationSet::Apply).chain(),
+        );
+        app.add_systems(
+            Update,
+            (update_layout, update_progress, drive_timelines)
+                .chain()
+                .in_set(AnimationSet::Progress),
+        );
+        app.add_systems(
+            Update,
+            (interaction_transition, play_enter, apply_stagger).before(AnimationSet::Progress),
+        );
+        app.add_systems(
+            Update,
+            (despawn_exited, propagate_opacity).after(AnimationSet::Apply),
+        );
+        app.add_systems(Update, restore_opacity.before(AnimationSet::Apply));
+        app.add_systems(Update, animate_reflect.in_set(AnimationSet::Apply));
+        app.add_systems(PostUpdate, finish_layout.after(UiSystem::Layout));
+        app.register_lens::<StyleWidthLens>()
+            .register_lens::<TextColorLens>()
+            .register_lens::<BackgroundColorLens>()
+            .register_lens::<BorderColorLens>()
+            .register_lens::<OutlineColorLens>()
+            .register_lens::<TransformTranslationLens>()
+            .register_lens::<SpriteColorLens>()
+            .register_lens::<OpacityLens>();
     }
 }
 
-fn update_progress(mut query: Query<&mut Animator>, time: Res<Time>) {
-    for mut animator in query.iter_mut() {
-        animator.tick_progress(time.delta());
-    }
+/// Component types that already have an [`animate_component`] system.
+#[derive(Resource, Default)]
+struct AnimatedComponents(HashSet<TypeId>);
+
+pub trait AnimationApp {
+    /// Makes [`Animator`]s write the properties of `L::Target`, needed once per component
+    /// type for lenses outside this crate.
+    fn register_lens<L: Lens>(&mut self) -> &mut Self;
+    /// Plays the [`StateAnimations`](crate::state::StateAnimations) of `S` on state
+    /// changes and lets [`AnimatedNextState`] wait for exit animations.
+    fn add_state_animations<S: States>(&mut self) -> &mut Self;
 }
 
-fn update_style(mut query: Query<(&mut Animator, &mut Style)>) {
-    for (mut animator, mut style) in query.iter_mut() {
-        animator.tick_style(&mut style);
+impl AnimationApp for App {
+    fn register_lens<L: Lens>(&mut self) -> &mut Self {
+        let mut registered = self
+            .world
+            .get_resource_or_insert_with(AnimatedComponents::default);
+        if registered.0.insert(TypeId::of::<L::Target>()) {
+            self.add_systems(
+                Update,
+                animate_component::<L::Target>.in_set(AnimationSet::Apply),
+            );
+        }
+        self
+    }
+
+    fn add_state_animations<S: States>(&mut self) -> &mut Self {
+        self.init_resource::<AnimatedNextState<S>>();
+        self.add_systems(
+            Update,
+            (delay_state_change::<S>, state_transition::<S>)
+                .chain()
+                .before(AnimationSet::Progress),
+        );
+        self
     }
 }
 
-fn update_text(mut query: Query<(&mut Animator, &mut Text)>) {
-    for (mut animator, mut text) in query.iter_mut() {
-        animator.tick_text(&mut text);
+/// Refreshes the sizes UI animators convert [`Val`] units against, holding the ones whose
+/// node was just spawned until it is laid out.
+fn update_layout(
+    mut query: Query<(&mut Animator, Ref<Node>, Option<&Parent>)>,
+    nodes: Query<&Node>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    let viewport = windows.get_single().map_or(Vec2::ZERO, |window| {
+        Vec2::new(window.width(), window.height())
+    });
+    for (mut animator, node, parent) in query.iter_mut() {
+        let parent = parent
+            .and_then(|parent| nodes.get(parent.get()).ok())
+            .map_or(viewport, Node::size);
+        if node.is_added() && node.size() == Vec2::ZERO {
+            animator.await_layout(true);
+        }
+        animator.set_layout(ValContext {
+            size: node.size(),
+            parent,
+            viewport,
+        });
     }
 }
 
-fn update_transform(mut query: Query<(&mut Animator, &mut Transform)>) {
-    for (mut animator, mut transform) in query.iter_mut() {
-        animator.tick_transform(&mut transform);
+fn finish_layout(mut query: Query<&mut Animator, With<Node>>) {
+    for mut animator in query.iter_mut() {
+        if animator.is_awaiting_layout() {
+            animator.await_layout(false);
+        }
     }
 }
 
-fn update_background(mut query: Query<(&mut Animator, &mut BackgroundColor)>) {
-    for (mut animator, mut background) in query.iter_mut() {
-        animator.tick_background(&mut background);
+fn update_progress(
+    mut query: Query<(Entity, &mut Animator)>,
+    time: Res<Time>,
+    mut events: EventWriter<AnimatorEvent>,
+) {
+    for (entity, mut animator) in query.iter_mut() {
+        animator.tick_progress(time.delta());
+        events.send_batch(
+            animator
+                .drain_events()
+                .map(|kind| AnimatorEvent { entity, kind }),
+        );
     }
 }
 
-fn update_sprite(mut query: Query<(&mut Animator, &mut Sprite)>) {
-    for (mut animator, mut sprite) in query.iter_mut() {
-        animator.tick_sprite(&mut sprite);
+pub fn animate_component<C: Component>(mut query: Query<(&mut Animator, &mut C)>) {
+    for (mut animator, mut component) in query.iter_mut() {
+        if animator.animates::<C>() {
+            animator.tick_component(component.as_mut());
+        }
     }
 }
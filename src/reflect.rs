@@ -0,0 +1,364 @@
+use crate::{
+    ani_utils::ColorSpace,
+    animator::{Animator, EaseMethod, Spring},
+    lens::{Animatable, Curve, StartMap, Track, TrackKey, TrackMode, ValAxis, ValContext},
+};
+use bevy::{prelude::*, reflect::GetPath, utils::HashSet};
+use std::{
+    any::{Any, TypeId},
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+/// Leaf value of a reflected field an [`Animator`] can interpolate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReflectValue {
+    F32(f32),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+    Color(Color),
+    Val(Val),
+    Quat(Quat),
+}
+
+macro_rules! reflect_value_from {
+    ($($variant:ident),*) => {
+        $(impl From<$variant> for ReflectValue {
+            fn from(value: $variant) -> Self {
+                ReflectValue::$variant(value)
+            }
+        })*
+    };
+}
+
+reflect_value_from!(Vec2, Vec3, Vec4, Color, Val, Quat);
+
+impl From<f32> for ReflectValue {
+    fn from(value: f32) -> Self {
+        ReflectValue::F32(value)
+    }
+}
+
+impl ReflectValue {
+    /// Reads a supported leaf, `None` for any other type.
+    pub fn from_reflect(leaf: &dyn Reflect) -> Option<Self> {
+        let any = leaf.as_any();
+        any.downcast_ref::<f32>()
+            .map(|&v| ReflectValue::F32(v))
+            .or_else(|| any.downcast_ref::<Vec2>().map(|&v| ReflectValue::Vec2(v)))
+            .or_else(|| any.downcast_ref::<Vec3>().map(|&v| ReflectValue::Vec3(v)))
+            .or_else(|| any.downcast_ref::<Vec4>().map(|&v| ReflectValue::Vec4(v)))
+            .or_else(|| any.downcast_ref::<Color>().map(|&v| ReflectValue::Color(v)))
+            .or_else(|| any.downcast_ref::<Val>().map(|&v| ReflectValue::Val(v)))
+            .or_else(|| any.downcast_ref::<Quat>().map(|&v| ReflectValue::Quat(v)))
+    }
+
+    /// Writes into a leaf of the same type, other leaves are left untouched.
+    pub fn write(self, leaf: &mut dyn Reflect) {
+        let any = leaf.as_any_mut();
+        match self {
+            ReflectValue::F32(v) => write_leaf(any, v),
+            ReflectValue::Vec2(v) => write_leaf(any, v),
+            ReflectValue::Vec3(v) => write_leaf(any, v),
+            ReflectValue::Vec4(v) => write_leaf(any, v),
+            ReflectValue::Color(v) => write_leaf(any, v),
+            ReflectValue::Val(v) => write_leaf(any, v),
+            ReflectValue::Quat(v) => write_leaf(any, v),
+        }
+    }
+
+    fn zip(self, rhs: Self, f: impl Fn(ReflectValue, ReflectValue) -> Option<Self>) -> Self {
+        f(self, rhs).unwrap_or(self)
+    }
+}
+
+fn write_leaf<T: 'static>(leaf: &mut dyn Any, value: T) {
+    if let Some(leaf) = leaf.downcast_mut::<T>() {
+        *leaf = value;
+    }
+}
+
+macro_rules! zip_variants {
+    ($a:expr, $b:expr, |$x:ident, $y:ident| $body:expr) => {
+        match ($a, $b) {
+            (ReflectValue::F32($x), ReflectValue::F32($y)) => Some(ReflectValue::F32($body)),
+            (ReflectValue::Vec2($x), ReflectValue::Vec2($y)) => Some(ReflectValue::Vec2($body)),
+            (ReflectValue::Vec3($x), ReflectValue::Vec3($y)) => Some(ReflectValue::Vec3($body)),
+            (ReflectValue::Vec4($x), ReflectValue::Vec4($y)) => Some(ReflectValue::Vec4($body)),
+            (ReflectValue::Color($x), ReflectValue::Color($y)) => Some(ReflectValue::Color($body)),
+            (ReflectValue::Val($x), ReflectValue::Val($y)) => Some(ReflectValue::Val($body)),
+            (ReflectValue::Quat($x), ReflectValue::Quat($y)) => Some(ReflectValue::Quat($body)),
+            _ => None,
+        }
+    };
+}
+
+/// Mismatched variants keep the left hand side.
+impl Animatable for ReflectValue {
+    fn add(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| zip_variants!(a, b, |x, y| x.add(y)))
+    }
+
+    fn sub(self, rhs: Self) -> Self {
+        self.zip(rhs, |a, b| zip_variants!(a, b, |x, y| x.sub(y)))
+    }
+
+    fn scale(self, ratio: f32) -> Self {
+        match self {
+            ReflectValue::F32(v) => ReflectValue::F32(v.scale(ratio)),
+            ReflectValue::Vec2(v) => ReflectValue::Vec2(v.scale(ratio)),
+            ReflectValue::Vec3(v) => ReflectValue::Vec3(v.scale(ratio)),
+            ReflectValue::Vec4(v) => ReflectValue::Vec4(v.scale(ratio)),
+            ReflectValue::Color(v) => ReflectValue::Color(v.scale(ratio)),
+            ReflectValue::Val(v) => ReflectValue::Val(v.scale(ratio)),
+            ReflectValue::Quat(v) => ReflectValue::Quat(v.scale(ratio)),
+        }
+    }
+
+    fn norm(self) -> f32 {
+        match self {
+            ReflectValue::F32(v) => v.norm(),
+            ReflectValue::Vec2(v) => v.norm(),
+            ReflectValue::Vec3(v) => v.norm(),
+            ReflectValue::Vec4(v) => v.norm(),
+            ReflectValue::Color(v) => v.norm(),
+            ReflectValue::Val(v) => v.norm(),
+            ReflectValue::Quat(v) => v.norm(),
+        }
+    }
+
+    fn match_unit(self, like: Self, context: &ValContext, axis: ValAxis) -> Self {
+        match (self, like) {
+            (ReflectValue::Val(v), ReflectValue::Val(like)) => {
+                ReflectValue::Val(v.match_unit(like, context, axis))
+            }
+            _ => self,
+        }
+    }
+
+    fn lerp(self, to: Self, ratio: f32) -> Self {
+        self.zip(to, |a, b| zip_variants!(a, b, |x, y| x.lerp(y, ratio)))
+    }
+
+    fn lerp_in(self, to: Self, ratio: f32, space: ColorSpace) -> Self {
+        self.zip(to, |a, b| {
+            zip_variants!(a, b, |x, y| x.lerp_in(y, ratio, space))
+        })
+    }
+
+    fn rotation_mut(&mut self) -> Option<&mut Quat> {
+        match self {
+            ReflectValue::Quat(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Animates the field at `"Component::field.path"` of any reflected component.
+#[derive(Debug)]
+pub(crate) struct ReflectTrack {
+    pub path: Arc<str>,
+    pub curve: Curve<ReflectValue>,
+    pub mode: TrackMode,
+    pub ease: Option<EaseMethod>,
+    pub spring: Option<Spring>,
+    pub space: ColorSpace,
+}
+
+impl Track for ReflectTrack {
+    /// Reflected fields are written by [`animate_reflect`] rather than a component system.
+    fn component(&self) -> TypeId {
+        TypeId::of::<ReflectTrack>()
+    }
+
+    fn key(&self) -> TrackKey {
+        let mut hasher = DefaultHasher::new();
+        self.path.hash(&mut hasher);
+        (TypeId::of::<ReflectTrack>(), hasher.finish())
+    }
+
+    fn mode(&self) -> TrackMode {
+        self.mode
+    }
+
+    fn ease(&self) -> Option<&EaseMethod> {
+        self.ease.as_ref()
+    }
+
+    fn set_ease(&mut self, ease: EaseMethod) {
+        self.ease = Some(ease);
+    }
+
+    fn spring(&self) -> Option<&Spring> {
+        self.spring.as_ref()
+    }
+
+    fn set_spring(&mut self, spring: Spring) {
+        self.spring = Some(spring);
+    }
+
+    fn set_color_space(&mut self, space: ColorSpace) {
+        self.space = space;
+    }
+
+    fn reflect_path(&self) -> Option<&Arc<str>> {
+        Some(&self.path)
+    }
+
+    fn capture(&self, component: &dyn Any, start: &mut StartMap) {
+        if let Some(&value) = component.downcast_ref::<ReflectValue>() {
+            start.insert(self.key(), value);
+        }
+    }
+
+    fn apply(&self, component: &mut dyn Any, start: &StartMap, ratio: f32) {
+        let (Some(component), Some(from)) = (
+            component.downcast_mut::<ReflectValue>(),
+            start.get::<ReflectValue>(&self.key()),
+        ) else {
+            return;
+        };
+        *component = self.curve.sample(self.mode, from, ratio, self.space);
+    }
+
+    fn add_offset(&self, component: &mut dyn Any, start: &StartMap, ratio: f32) {
+        let (Some(component), Some(from)) = (
+            component.downcast_mut::<ReflectValue>(),
+            start.get::<ReflectValue>(&self.key()),
+        ) else {
+            return;
+        };
+        *component = component.add(self.curve.offset(self.mode, from, ratio, self.space));
+    }
+
+    fn shift(&self, start: &mut StartMap, ratio: f32, undo: bool) {
+        let key = self.key();
+        if let Some(from) = start.get::<ReflectValue>(&key) {
+            let start_value = self.curve.shifted(self.mode, from, ratio, self.space, undo);
+            start.insert(key, start_value);
+        }
+    }
+
+    fn copy_start(&self, from: &StartMap, to: &mut StartMap) {
+        let key = self.key();
+        if let Some(value) = from.get::<ReflectValue>(&key) {
+            to.insert(key, value);
+        }
+    }
+
+    /// Reflected paths have no axis, percentages refer to the parent's width.
+    fn resolve(&self, start: &mut StartMap, context: &ValContext) {
+        let key = self.key();
+        if let (Some(from), Some(like)) = (start.get::<ReflectValue>(&key), self.curve.first()) {
+            start.insert(key, from.match_unit(like, context, ValAxis::Horizontal));
+        }
+    }
+
+    fn blend(&self, component: &mut dyn Any, from: &StartMap, weight: f32) {
+        let (Some(component), Some(from)) = (
+            component.downcast_mut::<ReflectValue>(),
+            from.get::<ReflectValue>(&self.key()),
+        ) else {
+            return;
+        };
+        *component = from.lerp_in(*component, weight, self.space);
+    }
+
+    fn settle(
+        &self,
+        component: &mut dyn Any,
+        start: &StartMap,
+        velocity: &mut StartMap,
+        dt: f32,
+    ) -> bool {
+        let key = self.key();
+        let (Some(component), Some(from), Some(spring)) = (
+            component.downcast_mut::<ReflectValue>(),
+            start.get::<ReflectValue>(&key),
+            &self.spring,
+        ) else {
+            return true;
+        };
+        let target = self.curve.sample(self.mode, from, 1., self.space);
+        spring.settle(component, target, velocity, key, dt)
+    }
+}
+
+/// Resolves the reflect paths of every [`Animator`]'s current step and writes them back,
+/// paths that can't be resolved are reported once.
+pub fn animate_reflect(world: &mut World, mut warned: Local<HashSet<Arc<str>>>) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let mut query = world.query::<(Entity, &Animator)>();
+    let jobs: Vec<_> = query
+        .iter(world)
+        .flat_map(|(entity, animator)| {
+            let mut written = Vec::new();
+            animator
+                .reflect_tracks()
+                .into_iter()
+                .map(move |(source, index, key, path)| {
+                    let additive = written.contains(&key);
+                    written.push(key);
+                    (entity, source, index, additive, path)
+                })
+        })
+        .collect();
+    let mut warn_once = |path: &Arc<str>, message: String| {
+        if warned.insert(path.clone()) {
+            warn!("reflect path `{path}` {message}");
+        }
+    };
+    for (entity, source, index, additive, path) in jobs {
+        let Some((type_path, field)) = path.rsplit_once("::") else {
+            warn_once(&path, "has no `Component::` prefix".into());
+            continue;
+        };
+        let Some(registration) = registry
+            .get_with_short_type_path(type_path)
+            .or_else(|| registry.get_with_type_path(type_path))
+        else {
+            warn_once(
+                &path,
+                format!("names `{type_path}`, which isn't registered"),
+            );
+            continue;
+        };
+        let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+            warn_once(
+                &path,
+                format!("needs `#[reflect(Component)]` on `{type_path}`"),
+            );
+            continue;
+        };
+        let Some(component) = reflect_component.reflect(world.entity(entity)) else {
+            continue;
+        };
+        let current = match component.reflect_path(field) {
+            Ok(leaf) => ReflectValue::from_reflect(leaf),
+            Err(error) => {
+                warn_once(&path, format!("can't be resolved: {error}"));
+                continue;
+            }
+        };
+        let Some(current) = current else {
+            warn_once(&path, "leads to a type that can't be animated".into());
+            continue;
+        };
+        let mut value = current;
+        if let Some(mut animator) = world.get_mut::<Animator>(entity) {
+            animator.tick_track(source, index, &mut value, additive);
+        }
+        if value == current {
+            continue;
+        }
+        let mut entity = world.entity_mut(entity);
+        if let Some(mut component) = reflect_component.reflect_mut(&mut entity) {
+            if let Ok(leaf) = component.reflect_path_mut(field) {
+                value.write(leaf);
+            }
+        }
+    }
+}
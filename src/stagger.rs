@@ -0,0 +1,94 @@
+use crate::animator::Animator;
+use bevy::prelude::*;
+use std::{fmt, sync::Arc, time::Duration};
+
+/// How the delay of each child grows with its position.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StaggerOrder {
+    /// First child starts first.
+    #[default]
+    Linear,
+    /// Last child starts first.
+    FromEnd,
+    /// Middle children start first, spreading outwards.
+    FromCenter,
+    /// Children laid out in rows of `columns` start by their distance to the cell at
+    /// index `from`.
+    Grid { columns: usize, from: usize },
+}
+
+impl StaggerOrder {
+    /// Multiple of the stagger delay for the child at `index` of `len`.
+    fn factor(&self, index: usize, len: usize) -> f32 {
+        match *self {
+            StaggerOrder::Linear => index as f32,
+            StaggerOrder::FromEnd => (len - 1 - index) as f32,
+            StaggerOrder::FromCenter => (index as f32 - (len - 1) as f32 / 2.).abs(),
+            StaggerOrder::Grid { columns, from } => {
+                let columns = columns.max(1);
+                let cell =
+                    |index: usize| Vec2::new((index % columns) as f32, (index / columns) as f32);
+                cell(index).distance(cell(from))
+            }
+        }
+    }
+}
+
+/// Gives every child of the entity an [`Animator`] built by the same function, each
+/// starting `delay` later than the previous one in [`StaggerOrder`].
+///
+/// Children that already have an [`Animator`] are left alone.
+#[derive(Component, Clone)]
+pub struct Stagger {
+    pub delay: Duration,
+    pub order: StaggerOrder,
+    animate: Arc<dyn Fn(&mut Animator) + Send + Sync>,
+}
+
+impl Stagger {
+    pub fn new(
+        delay: Duration,
+        order: StaggerOrder,
+        animate: impl Fn(&mut Animator) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            delay,
+            order,
+            animate: Arc::new(animate),
+        }
+    }
+}
+
+impl fmt::Debug for Stagger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stagger")
+            .field("delay", &self.delay)
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+/// Parents that gained a [`Stagger`] or new children.
+type StaggerChanged = Or<(Added<Stagger>, Changed<Children>)>;
+
+pub(crate) fn apply_stagger(
+    mut commands: Commands,
+    parents: Query<(&Stagger, &Children), StaggerChanged>,
+    animated: Query<(), With<Animator>>,
+) {
+    for (stagger, children) in parents.iter() {
+        for (index, &child) in children.iter().enumerate() {
+            if animated.contains(child) {
+                continue;
+            }
+            let mut animator = Animator::default();
+            (stagger.animate)(&mut animator);
+            animator.set_start_delay(
+                stagger
+                    .delay
+                    .mul_f32(stagger.order.factor(index, children.len())),
+            );
+            commands.entity(child).insert(animator);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{animator::Animator, lifecycle::Exiting};
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Steps of the entity's [`Animator`] played when the state `S` enters or exits a value,
+/// wired up by [`AnimationApp::add_state_animations`](crate::plugin::AnimationApp::add_state_animations).
+///
+/// A played step runs on into the following ones unless the [`Animator`] is set to
+/// [`Animator::set_step`], so states can also start a whole sequence.
+#[derive(Component, Clone, Debug)]
+pub struct StateAnimations<S: States> {
+    enter: Vec<(S, usize)>,
+    exit: Vec<(S, usize)>,
+}
+
+impl<S: States> Default for StateAnimations<S> {
+    fn default() -> Self {
+        Self {
+            enter: Vec::new(),
+            exit: Vec::new(),
+        }
+    }
+}
+
+impl<S: States> StateAnimations<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Plays step `index` when `state` is entered, like `OnEnter(state)`.
+    pub fn on_enter(mut self, state: S, index: usize) -> Self {
+        self.enter.push((state, index));
+        self
+    }
+
+    /// Plays step `index` when `state` is left, like `OnExit(state)`.
+    pub fn on_exit(mut self, state: S, index: usize) -> Self {
+        self.exit.push((state, index));
+        self
+    }
+
+    fn find(steps: &[(S, usize)], state: &S) -> Option<usize> {
+        steps
+            .iter()
+            .find(|(other, _)| other == state)
+            .map(|(_, index)| *index)
+    }
+}
+
+/// Like [`NextState`], but only changes the state once the exit animations of the
+/// current one completed.
+#[derive(Resource, Debug)]
+pub struct AnimatedNextState<S: States> {
+    pending: Option<S>,
+    exiting: bool,
+    /// State whose exit animations already played before the change.
+    exited: Option<S>,
+}
+
+impl<S: States> Default for AnimatedNextState<S> {
+    fn default() -> Self {
+        Self {
+            pending: None,
+            exiting: false,
+            exited: None,
+        }
+    }
+}
+
+impl<S: States> AnimatedNextState<S> {
+    pub fn set(&mut self, state: S) {
+        self.pending = Some(state);
+    }
+}
+
+fn play(animator: &mut Animator, index: usize) {
+    animator.resume();
+    animator.transition(index, Duration::ZERO);
+}
+
+/// Plays the exit animations of the current state, then hands the pending state over
+/// to [`NextState`] once they completed.
+pub(crate) fn delay_state_change<S: States>(
+    state: Res<State<S>>,
+    mut delayed: ResMut<AnimatedNextState<S>>,
+    mut next: ResMut<NextState<S>>,
+    mut query: Query<(&StateAnimations<S>, &mut Animator), Without<Exiting>>,
+) {
+    let Some(pending) = delayed.pending.clone() else {
+        return;
+    };
+    if !delayed.exiting {
+        for (animations, mut animator) in query.iter_mut() {
+            if let Some(index) = StateAnimations::find(&animations.exit, state.get()) {
+                play(&mut animator, index);
+            }
+        }
+        delayed.exiting = true;
+        return;
+    }
+    let complete = query.iter().all(|(animations, animator)| {
+        StateAnimations::find(&animations.exit, state.get()).is_none()
+            || animator.is_step_complete()
+    });
+    if complete {
+        next.set(pending);
+        delayed.pending = None;
+        delayed.exiting = false;
+        delayed.exited = Some(state.get().clone());
+    }
+}
+
+/// Plays the exit and enter animations whenever `S` changes.
+pub(crate) fn state_transition<S: States>(
+    state: Res<State<S>>,
+    mut last: Local<Option<S>>,
+    mut delayed: ResMut<AnimatedNextState<S>>,
+    mut query: Query<(&StateAnimations<S>, &mut Animator), Without<Exiting>>,
+) {
+    let current = state.get();
+    if last.as_ref() == Some(current) {
+        return;
+    }
+    let exited = delayed.exited.take();
+    for (animations, mut animator) in query.iter_mut() {
+        let exit = last
+            .as_ref()
+            .filter(|&last| exited.as_ref() != Some(last))
+            .and_then(|last| StateAnimations::find(&animations.exit, last));
+        if let Some(index) = StateAnimations::find(&animations.enter, current).or(exit) {
+            play(&mut animator, index);
+        }
+    }
+    *last = Some(current.clone());
+}
@@ -0,0 +1,211 @@
+use crate::animator::Animator;
+use bevy::{prelude::*, utils::HashMap};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+enum TimelineItem {
+    Entity(Entity),
+    Group(TimelineGroup),
+}
+
+/// Entities' [`Animator`]s laid out one after another or all at once.
+///
+/// An entity takes the room of its [`Animator::duration`], infinitely repeating ones
+/// take none.
+#[derive(Clone, Debug, Default)]
+pub struct TimelineGroup {
+    parallel: bool,
+    items: Vec<(TimelineItem, f32)>,
+    labels: Vec<(String, usize)>,
+}
+
+impl TimelineGroup {
+    /// Each item starts when the previous one ended.
+    pub fn sequence() -> Self {
+        Self::default()
+    }
+
+    /// Every item starts with the group.
+    pub fn parallel() -> Self {
+        Self {
+            parallel: true,
+            ..default()
+        }
+    }
+
+    /// Adds the animator of `entity`, starting `offset` after its position in the group.
+    pub fn add(mut self, entity: Entity, offset: Duration) -> Self {
+        self.items
+            .push((TimelineItem::Entity(entity), offset.as_secs_f32()));
+        self
+    }
+
+    pub fn add_group(mut self, group: TimelineGroup, offset: Duration) -> Self {
+        self.items
+            .push((TimelineItem::Group(group), offset.as_secs_f32()));
+        self
+    }
+
+    /// Names the position where the next item would start, for [`Timeline::seek_label`].
+    pub fn label(mut self, name: impl Into<String>) -> Self {
+        self.labels.push((name.into(), self.items.len()));
+        self
+    }
+
+    /// Places every entity of the group starting at `start`, returns where the group ends.
+    fn layout(
+        &self,
+        start: f32,
+        duration: &impl Fn(Entity) -> f32,
+        placed: &mut Vec<(Entity, f32)>,
+        labels: &mut HashMap<String, f32>,
+    ) -> f32 {
+        let mut cursor = start;
+        let mut end = start;
+        for (index, (item, offset)) in self.items.iter().enumerate() {
+            self.place_labels(index, cursor, labels);
+            let item_start = cursor + offset;
+            let item_end = match item {
+                TimelineItem::Entity(entity) => {
+                    placed.push((*entity, item_start));
+                    item_start + duration(*entity)
+                }
+                TimelineItem::Group(group) => group.layout(item_start, duration, placed, labels),
+            };
+            end = end.max(item_end);
+            if !self.parallel {
+                cursor = item_end;
+            }
+        }
+        self.place_labels(self.items.len(), cursor, labels);
+        end
+    }
+
+    fn place_labels(&self, index: usize, at: f32, labels: &mut HashMap<String, f32>) {
+        for (name, _) in self.labels.iter().filter(|(_, other)| *other == index) {
+            labels.insert(name.clone(), at);
+        }
+    }
+}
+
+/// Plays the [`Animator`]s of several entities as one unit by seeking them to their
+/// place on a shared clock, the animators are paused while the timeline drives them.
+///
+/// Parallel animations follow the same clock, springs keep settling in real time.
+#[derive(Component, Debug)]
+pub struct Timeline {
+    root: TimelineGroup,
+    elapsed: f32,
+    duration: f32,
+    speed: f32,
+    playing: bool,
+    pending_label: Option<String>,
+    applied: Option<f32>,
+}
+
+impl Timeline {
+    pub fn new(root: TimelineGroup) -> Self {
+        Self {
+            root,
+            elapsed: 0.,
+            duration: 0.,
+            speed: 1.,
+            playing: false,
+            pending_label: None,
+            applied: None,
+        }
+    }
+
+    pub fn play(&mut self) -> &mut Self {
+        self.playing = true;
+        self
+    }
+
+    pub fn pause(&mut self) -> &mut Self {
+        self.playing = false;
+        self
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Playback rate multiplier, negative values play the timeline backwards.
+    pub fn set_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Flips the playback direction and keeps playing from the current position.
+    pub fn reverse(&mut self) -> &mut Self {
+        self.speed = -self.speed;
+        self.playing = true;
+        self
+    }
+
+    pub fn seek(&mut self, time: Duration) {
+        self.elapsed = time.as_secs_f32();
+        self.pending_label = None;
+    }
+
+    /// Jumps to a [`TimelineGroup::label`] the next time the timeline updates.
+    pub fn seek_label(&mut self, name: impl Into<String>) {
+        self.pending_label = Some(name.into());
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs_f32(self.elapsed)
+    }
+
+    /// Length of the timeline as of its last update.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f32(self.duration)
+    }
+}
+
+pub(crate) fn drive_timelines(
+    time: Res<Time>,
+    mut timelines: Query<&mut Timeline>,
+    mut animators: Query<&mut Animator>,
+) {
+    for mut timeline in timelines.iter_mut() {
+        let timeline = timeline.as_mut();
+        let mut placed = Vec::new();
+        let mut labels = HashMap::default();
+        let duration = |entity| {
+            animators
+                .get(entity)
+                .ok()
+                .and_then(|animator| animator.duration())
+                .map_or(0., |duration| duration.as_secs_f32())
+        };
+        timeline.duration = timeline
+            .root
+            .layout(0., &duration, &mut placed, &mut labels);
+        if let Some(at) = timeline
+            .pending_label
+            .take()
+            .and_then(|label| labels.get(&label).copied())
+        {
+            timeline.elapsed = at;
+        }
+        if timeline.playing {
+            timeline.elapsed += time.delta_seconds() * timeline.speed;
+            if timeline.elapsed <= 0. || timeline.elapsed >= timeline.duration {
+                timeline.playing = false;
+            }
+        }
+        timeline.elapsed = timeline.elapsed.clamp(0., timeline.duration);
+        let moved = timeline.applied != Some(timeline.elapsed);
+        timeline.applied = Some(timeline.elapsed);
+        for (entity, start) in placed {
+            if let Ok(mut animator) = animators.get_mut(entity) {
+                animator.pause();
+                if moved {
+                    animator.seek(Duration::from_secs_f32((timeline.elapsed - start).max(0.)));
+                }
+                animator.settle_paused(time.delta());
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{
     animator::Animator,
+    interaction::InteractionAnimations,
+    stagger::Stagger,
     ui::builder::{Class, UiBuilder},
 };
 use bevy::prelude::*;
@@ -36,6 +38,16 @@ pub fn na<P, A>(
         .id()
 }
 
+/// Node whose children are animated by `stagger` instead of an [`Animator`] each.
+pub fn nas<P>(
+    class: impl Class<P, In = NodeBundle>,
+    stagger: Stagger,
+    commands: &mut UiBuilder,
+    children: impl FnOnce(&mut UiBuilder),
+) -> Entity {
+    nodei(class, stagger, commands, children)
+}
+
 pub fn sa<P, A>(
     class: impl Class<P, In = SpriteBundle>,
     animate_class: impl Class<A, In = Animator>,
@@ -126,6 +138,22 @@ pub fn button<P>(
     buttoni(class, (), commands, children)
 }
 
+/// Button whose `animate_class` steps play on [`Interaction`] changes, in the order of
+/// [`InteractionAnimations::default`]: none, hovered, pressed. Missing steps are skipped
+/// and each step stops at its end unless `animate_class` calls `set_step(false)`.
+pub fn buttona<P, A>(
+    class: impl Class<P, In = ButtonBundle>,
+    animate_class: impl Class<A, In = Animator>,
+    commands: &mut UiBuilder,
+    children: impl FnOnce(&mut UiBuilder),
+) -> Entity {
+    let mut animator = Animator::default();
+    animator.set_step(true);
+    animate_class.apply(&mut animator, commands.world);
+    let animations = InteractionAnimations::for_steps(animator.step_count());
+    buttoni(class, (animator, animations), commands, children)
+}
+
 pub fn text_buttoni<P, P1>(
     txt: impl Into<String>,
     class: impl Class<P, In = ButtonBundle>,
@@ -147,6 +175,18 @@ pub fn text_button<P, P1>(
     text_buttoni(txt, class, text_class, (), commands)
 }
 
+pub fn text_buttona<P, P1, A>(
+    txt: impl Into<String>,
+    class: impl Class<P, In = ButtonBundle>,
+    text_class: impl Class<P1, In = TextStyle>,
+    animate_class: impl Class<A, In = Animator>,
+    commands: &mut UiBuilder,
+) -> Entity {
+    buttona(class, animate_class, commands, |p| {
+        text(txt, (), text_class, p);
+    })
+}
+
 pub fn atlas_imagei<P>(
     class: impl Class<P, In = AtlasImageBundle>,
     ext: impl Bundle,